@@ -0,0 +1,17 @@
+-- Schema as it existed before migrations were tracked in the repository.
+create table if not exists users (
+    id uuid primary key,
+    name text not null,
+    email text not null,
+    role text not null,
+    password_hash text not null,
+    token_salt text
+);
+
+create table if not exists chats (
+    id uuid primary key,
+    name text not null,
+    users_count bigint not null,
+    location text not null,
+    description text not null
+);
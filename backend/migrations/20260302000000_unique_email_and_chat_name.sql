@@ -0,0 +1,37 @@
+-- Emails are stored normalised (trimmed, lower-cased) from now on. Rows that
+-- only differ in case or spacing have to be merged by hand first: which
+-- account or chat survives is not ours to guess.
+do $$
+declare
+    duplicates text;
+begin
+    select string_agg(email, ', ') into duplicates
+    from (
+        select lower(btrim(email)) as email from users
+        group by 1 having count(*) > 1
+    ) d;
+
+    if duplicates is not null then
+        raise exception 'users share an email once normalised: %', duplicates
+            using hint = 'Merge or rename these accounts, then restart.';
+    end if;
+
+    select string_agg(format('%s (%s)', name, location), ', ') into duplicates
+    from (
+        select lower(btrim(name)) as name, lower(btrim(location)) as location from chats
+        group by 1, 2 having count(*) > 1
+    ) d;
+
+    if duplicates is not null then
+        raise exception 'chats share a name and location: %', duplicates
+            using hint = 'Rename or delete the extra chats, then restart.';
+    end if;
+end
+$$;
+
+update users set email = lower(btrim(email));
+
+create unique index if not exists users_email_key on users (email);
+
+create unique index if not exists chats_name_location_key
+    on chats (lower(btrim(name)), lower(btrim(location)));
@@ -0,0 +1,9 @@
+-- Account status for filtering, creation time for the default listing order.
+alter table users add column if not exists status text not null default 'active';
+alter table users add column if not exists created_at timestamptz not null default now();
+
+-- Keyset pagination and prefix search for the user listing.
+create index if not exists users_created_at_id_idx on users (created_at, id);
+create index if not exists users_lower_name_id_idx on users (lower(name), id);
+create index if not exists users_lower_name_prefix_idx on users (lower(name) text_pattern_ops);
+create index if not exists users_email_prefix_idx on users (email text_pattern_ops);
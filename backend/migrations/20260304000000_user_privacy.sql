@@ -0,0 +1,3 @@
+-- Profile fields users choose to show to other (non-admin) users.
+alter table users add column if not exists show_email boolean not null default false;
+alter table users add column if not exists show_role boolean not null default true;
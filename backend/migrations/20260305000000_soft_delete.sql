@@ -0,0 +1,17 @@
+-- Deletions leave a tombstone; rows are hard-deleted by the purge job later.
+alter table users add column if not exists deleted_at timestamptz;
+alter table chats add column if not exists deleted_at timestamptz;
+
+-- Deleted rows no longer reserve their email or chat name; restoring one
+-- that has been taken in the meantime reports a conflict.
+-- Databases older than the migrations may hold it as a constraint instead
+alter table users drop constraint if exists users_email_key;
+drop index if exists users_email_key;
+create unique index if not exists users_email_live_key on users (email) where deleted_at is null;
+
+drop index if exists chats_name_location_key;
+create unique index if not exists chats_name_location_live_key
+    on chats (lower(btrim(name)), lower(btrim(location))) where deleted_at is null;
+
+create index if not exists users_deleted_at_idx on users (deleted_at) where deleted_at is not null;
+create index if not exists chats_deleted_at_idx on chats (deleted_at) where deleted_at is not null;
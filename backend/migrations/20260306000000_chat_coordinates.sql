@@ -0,0 +1,13 @@
+-- Optional WGS84 position of the area a chat covers.
+alter table chats add column if not exists latitude double precision;
+alter table chats add column if not exists longitude double precision;
+
+alter table chats add constraint chats_coordinates_check check (
+    (latitude is null) = (longitude is null)
+    and latitude between -90 and 90
+    and longitude between -180 and 180
+);
+
+-- Bounding-box prefilter for nearby search.
+create index if not exists chats_coordinates_idx on chats (latitude, longitude)
+    where latitude is not null and deleted_at is null;
@@ -0,0 +1,16 @@
+-- Location tree: city > district > street > building.
+create table if not exists locations (
+    id uuid primary key,
+    parent_id uuid references locations (id) on delete restrict,
+    kind text not null check (kind in ('city', 'district', 'street', 'building')),
+    name text not null
+);
+
+-- Siblings have distinct names; cities are compared among themselves.
+create unique index if not exists locations_parent_name_key
+    on locations (coalesce(parent_id, '00000000-0000-0000-0000-000000000000'::uuid), lower(btrim(name)));
+
+-- Free-text `location` stays as the display label; this links the chat into the tree.
+alter table chats add column if not exists location_id uuid references locations (id) on delete set null;
+
+create index if not exists chats_location_id_idx on chats (location_id) where deleted_at is null;
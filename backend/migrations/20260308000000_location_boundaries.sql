@@ -0,0 +1,16 @@
+-- District areas as GeoJSON (multi)polygon coordinates, [lon, lat] positions.
+alter table locations add column if not exists boundary jsonb;
+
+-- Bounding box of the outer rings, to narrow point lookups before the exact test.
+alter table locations
+    add column if not exists min_lat double precision,
+    add column if not exists max_lat double precision,
+    add column if not exists min_lon double precision,
+    add column if not exists max_lon double precision;
+
+alter table locations
+    add constraint locations_boundary_district_check check (boundary is null or kind = 'district');
+
+create index if not exists locations_boundary_bbox_idx
+    on locations (min_lat, max_lat, min_lon, max_lon)
+    where boundary is not null;
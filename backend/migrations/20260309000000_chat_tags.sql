@@ -0,0 +1,24 @@
+-- One fixed category per chat; the list mirrors `ChatCategory`.
+alter table chats add column if not exists category text not null default 'general';
+
+alter table chats add constraint chats_category_check check (
+    category in ('general', 'neighbours', 'education', 'pets', 'sport', 'marketplace', 'transport', 'events')
+);
+
+create index if not exists chats_category_idx on chats (category) where deleted_at is null;
+
+-- Admin-managed vocabulary; names are stored lower-cased.
+create table if not exists tags (
+    id uuid primary key,
+    name text not null
+);
+
+create unique index if not exists tags_name_key on tags (name);
+
+create table if not exists chat_tags (
+    chat_id uuid not null references chats (id) on delete cascade,
+    tag_id uuid not null references tags (id) on delete cascade,
+    primary key (chat_id, tag_id)
+);
+
+create index if not exists chat_tags_tag_id_idx on chat_tags (tag_id);
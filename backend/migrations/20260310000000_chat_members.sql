@@ -0,0 +1,22 @@
+create table if not exists chat_members (
+    chat_id uuid not null references chats (id) on delete cascade,
+    user_id uuid not null references users (id) on delete cascade,
+    role text not null default 'member' check (role in ('owner', 'moderator', 'member')),
+    joined_at timestamptz not null default now(),
+    primary key (chat_id, user_id)
+);
+
+create index if not exists chat_members_user_id_idx on chat_members (user_id);
+
+-- Member pages are keyed by join time.
+create index if not exists chat_members_joined_idx on chat_members (chat_id, joined_at, user_id);
+
+-- At most one owner per chat.
+create unique index if not exists chat_members_owner_key on chat_members (chat_id) where role = 'owner';
+
+-- Counted from `chat_members` from now on. The admin-entered counts can't be
+-- turned into members, since nothing records who those people were, so they
+-- are kept as a floor for the member count until real joins overtake them.
+-- Nothing records who created a chat either: no chat gets an owner here.
+alter table chats rename column users_count to legacy_users_count;
+alter table chats alter column legacy_users_count set default 0;
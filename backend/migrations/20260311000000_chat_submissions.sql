@@ -0,0 +1,38 @@
+-- Chats proposed by residents, listed once an admin approves them.
+create table if not exists chat_submissions (
+    id uuid primary key,
+    submitter_id uuid not null references users (id) on delete cascade,
+    name text not null,
+    location text not null,
+    description text not null default '',
+    latitude double precision,
+    longitude double precision,
+    location_id uuid references locations (id) on delete set null,
+    category text not null default 'general',
+    status text not null default 'pending'
+        check (status in ('pending', 'changes_requested', 'approved', 'rejected')),
+    review_note text,
+    chat_id uuid references chats (id) on delete set null,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    constraint chat_submissions_category_check check (
+        category in ('general', 'neighbours', 'education', 'pets', 'sport', 'marketplace', 'transport', 'events')
+    ),
+    constraint chat_submissions_coordinates_check check ((latitude is null) = (longitude is null))
+);
+
+-- The admin queue is read oldest first per status.
+create index if not exists chat_submissions_status_idx on chat_submissions (status, created_at);
+create index if not exists chat_submissions_submitter_idx on chat_submissions (submitter_id, created_at);
+
+create table if not exists notifications (
+    id uuid primary key,
+    user_id uuid not null references users (id) on delete cascade,
+    kind text not null,
+    submission_id uuid references chat_submissions (id) on delete cascade,
+    note text,
+    created_at timestamptz not null default now(),
+    read_at timestamptz
+);
+
+create index if not exists notifications_user_idx on notifications (user_id, created_at desc);
@@ -0,0 +1,11 @@
+-- Link to the messenger group, edited by the chat's owner or an admin.
+alter table chats add column if not exists invite_link text;
+
+-- Owners are the `owner` members; chats approved so far go to their submitters.
+insert into chat_members (chat_id, user_id, role)
+select s.chat_id, s.submitter_id, 'owner'
+from chat_submissions s
+join chats c on c.id = s.chat_id
+where s.status = 'approved'
+    and not exists (select 1 from chat_members m where m.chat_id = s.chat_id and m.role = 'owner')
+on conflict (chat_id, user_id) do update set role = 'owner';
@@ -0,0 +1,10 @@
+-- Follows of `/c/{id}`, one row per chat and UTC day.
+create table if not exists chat_link_clicks (
+    chat_id uuid not null references chats (id) on delete cascade,
+    day date not null,
+    clicks bigint not null default 0 check (clicks >= 0),
+    primary key (chat_id, day)
+);
+
+-- The report sums recent days across all chats.
+create index if not exists chat_link_clicks_day_idx on chat_link_clicks (day);
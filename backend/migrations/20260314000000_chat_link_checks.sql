@@ -0,0 +1,21 @@
+-- Every request of a chat's invite link by the dead-link checker.
+create table if not exists chat_link_checks (
+    id uuid primary key,
+    chat_id uuid not null references chats (id) on delete cascade,
+    link text not null,
+    outcome text not null check (outcome in ('ok', 'broken', 'unreachable')),
+    status_code integer,
+    error text,
+    checked_at timestamptz not null default now()
+);
+
+create index if not exists chat_link_checks_chat_idx on chat_link_checks (chat_id, checked_at desc);
+create index if not exists chat_link_checks_checked_at_idx on chat_link_checks (checked_at);
+
+-- Failures in a row of the link last checked; starts over when the link changes.
+create table if not exists chat_link_health (
+    chat_id uuid primary key references chats (id) on delete cascade,
+    link text not null,
+    failures integer not null default 0 check (failures >= 0),
+    checked_at timestamptz not null
+);
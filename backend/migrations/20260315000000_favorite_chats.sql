@@ -0,0 +1,9 @@
+-- Chats a user keeps at hand.
+create table if not exists favorite_chats (
+    user_id uuid not null references users (id) on delete cascade,
+    chat_id uuid not null references chats (id) on delete cascade,
+    created_at timestamptz not null default now(),
+    primary key (user_id, chat_id)
+);
+
+create index if not exists favorite_chats_user_idx on favorite_chats (user_id, created_at desc);
@@ -1,4 +1,5 @@
 pub mod api;
 pub mod db;
 pub mod crypto;
-pub mod ctx;
\ No newline at end of file
+pub mod ctx;
+pub mod http;
\ No newline at end of file
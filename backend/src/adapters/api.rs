@@ -1,12 +1,44 @@
 pub mod user;
 pub mod chat;
+pub mod location;
+pub mod tag;
+pub mod submission;
+pub mod notification;
+pub mod favorite;
 pub mod login;
 pub mod app_state;
 pub mod middlewares;
 pub mod ctx;
 pub mod errors;
+pub mod cookies;
+pub mod extractors;
+pub mod geojson;
+pub mod i18n;
+pub mod openapi;
 
-const AUTH_TOKEN: &'static str = "auth-token";
+use axum::{Json, http::{HeaderMap, HeaderName, HeaderValue}};
+
+use crate::application::pagination::Page;
+
+const AUTH_TOKEN: &str = "auth-token";
+const CSRF_TOKEN: &str = "csrf-token";
+pub const CSRF_HEADER: &str = "x-csrf-token";
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+pub const NEXT_CURSOR_HEADER: &str = "x-next-cursor";
+
+/// Page items as the body, the total and next cursor as headers.
+pub fn paginated<T>(page: Page<T>) -> (HeaderMap, Json<Vec<T>>) {
+    let mut headers = HeaderMap::new();
+
+    headers.insert(HeaderName::from_static(TOTAL_COUNT_HEADER), HeaderValue::from(page.total));
+
+    // Cursors are base64url, always a valid header value
+    if let Some(cursor) = page.next_cursor.and_then(|c| HeaderValue::from_str(&c).ok()) {
+        headers.insert(HeaderName::from_static(NEXT_CURSOR_HEADER), cursor);
+    }
+
+    (headers, Json(page.items))
+}
 
 // static PROTECTED_ROUTES: [(&str, &str); 1] = [
 //     ("/api/user", "DELETE"),
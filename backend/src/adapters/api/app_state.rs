@@ -1,8 +1,15 @@
 use std::sync::Arc;
 
-use crate::application::use_cases::UseCases;
+use crate::{adapters::api::cookies::CookieSettings, application::{link_check::LinkMonitor, use_cases::UseCases}};
 
 #[derive(Clone)]
 pub struct AppState {
-    pub use_cases: Arc<UseCases>
-}
\ No newline at end of file
+    pub use_cases: Arc<UseCases>,
+    pub link_monitor: Arc<LinkMonitor>,
+    pub cookies: CookieSettings,
+    /// Origins allowed to send state-changing requests besides the API host itself.
+    pub trusted_origins: Arc<Vec<String>>,
+    /// Raw invite links only for chat owners and admins, `/c/{id}` only for
+    /// signed-in users.
+    pub hide_invite_links: bool,
+}
@@ -1,66 +1,758 @@
-use axum::{Json, Router, extract::State, middleware, routing::{delete, get, post}};
+use axum::{Json, extract::State, http::{HeaderMap, header}, middleware, response::{IntoResponse, Redirect, Response}, routing::{get, post}};
 use serde::Deserialize;
 use serde_json::{Value, json};
+use utoipa::{IntoParams, ToSchema};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
 
-use crate::{adapters::api::{app_state::AppState, chat::chat_presenter::ChatPresenter, middlewares}, application::AppResult};
+use crate::{adapters::{api::{app_state::AppState, chat::chat_presenter::{ChatFacetsPresenter, ChatMemberPresenter, ChatPresenter, ChatViewer, DeadLinkPresenter, DeletedChatPresenter, FollowedChatPresenter, LinkCheckPresenter, NearbyChatPresenter}, extractors::{ValidatedJson, ValidatedPath, ValidatedQuery}, middlewares, openapi::{CHAT_TAG, ErrorResponse, StatusMessage}, paginated}, ctx::Ctx}, application::{AppResult, dto::chat::{LinkReportDTO, ListMembersDTO, TransferOwnershipDTO, UpdateChatDTO}, pagination::{Cursor, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE}, validation::{Validate, ValidationErrors}}, domain::entities::{chat::ChatCategory, user::Actor, value_objects::{ChatName, InviteLink, Location, TagName}}};
 
-pub fn chat_router() -> Router<AppState> {
-    let router = Router::new()
-        .route("/api/admin/chat", post(add_new_chat))
-        .route("/api/admin/chat", delete(delete_chat))
+pub const DESCRIPTION_MAX_LEN: usize = 2000;
+
+pub const DEFAULT_NEARBY_RADIUS_M: f64 = 1_000.0;
+pub const MAX_NEARBY_RADIUS_M: f64 = 50_000.0;
+pub const DEFAULT_NEARBY_LIMIT: u32 = 50;
+pub const MAX_NEARBY_LIMIT: u32 = 200;
+
+pub const MAX_CHAT_TAGS: usize = 10;
+
+pub const DEFAULT_LINK_REPORT_DAYS: u32 = 30;
+pub const MAX_LINK_REPORT_DAYS: u32 = 365;
+pub const DEFAULT_LINK_REPORT_LIMIT: u32 = 20;
+pub const MAX_LINK_REPORT_LIMIT: u32 = 100;
+
+fn validate_tags<'a>(errors: &mut ValidationErrors, tags: impl IntoIterator<Item = &'a str>, max: usize) {
+    let mut count = 0;
+
+    for tag in tags {
+        count += 1;
+
+        if TagName::parse(tag).is_err() {
+            errors.add("tags", "invalid_tag", format!("tags must be 1 to {} characters without commas", TagName::MAX_LEN));
+            return;
+        }
+    }
+
+    if count > max {
+        errors.add("tags", "too_many", format!("at most {max} tags are allowed"));
+    }
+}
+
+fn validate_invite_link(errors: &mut ValidationErrors, link: &str) {
+    if InviteLink::parse(link).is_err() {
+        errors.add("invite_link", "invalid_url", format!("invite_link must be an https address of at most {} characters", InviteLink::MAX_LEN));
+    }
+}
+
+pub fn chat_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(add_new_chat))
+        .routes(routes!(delete_chat))
+        .routes(routes!(get_deleted_chats))
+        .routes(routes!(restore_chat))
+        .routes(routes!(get_most_followed_chats))
+        .routes(routes!(get_dead_links))
+        .routes(routes!(get_link_checks))
+        .route_layer(middleware::from_fn(middlewares::require_admin))
+        .routes(routes!(get_chats))
+        .routes(routes!(update_chat))
+        .routes(routes!(transfer_ownership))
+        .routes(routes!(get_nearby_chats))
+        .routes(routes!(get_chat_facets))
+        .routes(routes!(join_chat))
+        .routes(routes!(leave_chat))
+        .routes(routes!(get_chat_members))
+        .route_layer(middleware::from_fn(middlewares::require_auth))
+        .routes(routes!(follow_invite_link))
+}
+
+/// Pre-v1 routes, see `legacy_user_router`.
+pub fn legacy_chat_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .route("/api/admin/chat", post(add_new_chat).delete(legacy_delete_chat))
         .route("/api/chats", get(get_chats))
-        .route_layer(middleware::from_fn(middlewares::require_auth));
+        .route_layer(middleware::from_fn(middlewares::require_auth))
+        .layer(middleware::map_response(middlewares::mark_deprecated))
+}
 
-    router
+/// Membership routes under the paths they were introduced with; these are new,
+/// so not deprecated like the rest of `/api/chats`.
+pub fn chat_membership_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .route("/api/chats/{id}/join", post(join_chat))
+        .route("/api/chats/{id}/leave", post(leave_chat))
+        .route("/api/chats/{id}/members", get(get_chat_members))
+        .route_layer(middleware::from_fn(middlewares::require_auth))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateChatPayload {
     pub name: String,
-    pub users_count: i64,
     pub location: String,
     pub description: String,
+    /// WGS84 degrees, given together with `longitude`
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Node of the location tree the chat belongs to
+    pub location_id: Option<Uuid>,
+    /// One of `ChatCategory`, `general` if omitted
+    pub category: Option<String>,
+    /// Names from the tag vocabulary
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// `https` link to the messenger group
+    pub invite_link: Option<String>,
+}
+
+impl Validate for CreateChatPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if errors.required("name", &self.name) {
+            errors.max_len("name", &self.name, ChatName::MAX_LEN);
+        }
+
+        if errors.required("location", &self.location) {
+            errors.max_len("location", &self.location, Location::MAX_LEN);
+        }
+
+        errors.max_len("description", &self.description, DESCRIPTION_MAX_LEN);
+        errors.coordinates(self.latitude, self.longitude);
+
+        if let Some(category) = &self.category {
+            errors.parse::<ChatCategory>("category", category);
+        }
+
+        validate_tags(&mut errors, self.tags.iter().map(String::as_str), MAX_CHAT_TAGS);
+
+        if let Some(link) = &self.invite_link {
+            validate_invite_link(&mut errors, link);
+        }
+
+        errors.into_result()
+    }
 }
 
+/// Add a chat to the directory (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/chats",
+    tag = CHAT_TAG,
+    request_body = CreateChatPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = ChatPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown `location_id` or tag", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 409, description = "`CHAT_NAME_TAKEN`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
 async fn add_new_chat(
     State(app_state): State<AppState>,
-    Json(payload): Json<CreateChatPayload>
+    ValidatedJson(payload): ValidatedJson<CreateChatPayload>
 ) -> AppResult<Json<ChatPresenter>> {
-    let chat = app_state.use_cases.add_new_chat(payload).await?;
+    let chat = app_state.use_cases.add_new_chat(payload.try_into()?).await?;
 
 
     Ok(
         Json(
-            chat
+            chat.into()
         )
     )
 }
 
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ChatsQuery {
+    /// Only chats in this location or any location below it
+    pub location_id: Option<Uuid>,
+    /// Only chats of this category
+    pub category: Option<String>,
+    /// Comma-separated; only chats carrying all of these tags
+    pub tags: Option<String>,
+}
+
+impl Validate for ChatsQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(category) = &self.category {
+            errors.parse::<ChatCategory>("category", category);
+        }
+
+        if let Some(tags) = &self.tags {
+            validate_tags(&mut errors, tags.split(','), MAX_CHAT_TAGS);
+        }
+
+        errors.into_result()
+    }
+}
+
 // #[cfg_attr(axum_macros::debug_handler, debug_handler)]
+/// List all chats, optionally within a location subtree
+#[utoipa::path(
+    get,
+    path = "/api/v1/chats",
+    tag = CHAT_TAG,
+    params(ChatsQuery),
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<ChatPresenter>),
+        (status = 400, description = "`INVALID_PARAMS`: unknown `location_id`", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
 async fn get_chats(
-    State(app_state): State<AppState>
+    State(app_state): State<AppState>,
+    viewer: ChatViewer,
+    ValidatedQuery(query): ValidatedQuery<ChatsQuery>,
 ) -> AppResult<Json<Vec<ChatPresenter>>> {
     let chats = app_state
         .use_cases
-        .get_chats()
+        .get_chats(query.try_into()?)
         .await?;
 
     let response = chats
         .into_iter()
-        .map(|c|c.into())
+        .map(|c| ChatPresenter::for_viewer(&viewer, c))
         .collect();
 
     Ok(Json(response))
 }
 
-#[derive(Deserialize)]
+/// Counts per category and per tag of the chats matching the filter
+#[utoipa::path(
+    get,
+    path = "/api/v1/chats/facets",
+    tag = CHAT_TAG,
+    params(ChatsQuery),
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = ChatFacetsPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown `location_id`", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn get_chat_facets(
+    State(app_state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<ChatsQuery>,
+) -> AppResult<Json<ChatFacetsPresenter>> {
+    let facets = app_state.use_cases.get_chat_facets(query.try_into()?).await?;
+
+    Ok(Json(facets.into()))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateChatPayload {
+    /// Admins only
+    pub category: Option<String>,
+    /// Replaces all tags of the chat; admins only
+    pub tags: Option<Vec<String>>,
+    pub description: Option<String>,
+    /// `https` link to the messenger group; an empty string removes it
+    pub invite_link: Option<String>,
+}
+
+impl Validate for UpdateChatPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.category.is_none() && self.tags.is_none() && self.description.is_none() && self.invite_link.is_none() {
+            errors.add("body", "required", "at least one of category, tags, description or invite_link must be given");
+        }
+
+        if let Some(description) = &self.description {
+            errors.max_len("description", description, DESCRIPTION_MAX_LEN);
+        }
+
+        if let Some(link) = self.invite_link.as_deref().filter(|l| !l.trim().is_empty()) {
+            validate_invite_link(&mut errors, link);
+        }
+
+        if let Some(category) = &self.category {
+            errors.parse::<ChatCategory>("category", category);
+        }
+
+        if let Some(tags) = &self.tags {
+            validate_tags(&mut errors, tags.iter().map(String::as_str), MAX_CHAT_TAGS);
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Edit a chat: its owner may change the description and invite link,
+/// admins anything
+#[utoipa::path(
+    patch,
+    path = "/api/v1/chats/{id}",
+    tag = CHAT_TAG,
+    params(("id" = Uuid, Path, description = "Chat id")),
+    request_body = UpdateChatPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = ChatPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown chat or tag", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`, also for a change the caller may not make", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn update_chat(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    viewer: ChatViewer,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<UpdateChatPayload>,
+) -> AppResult<Json<ChatPresenter>> {
+    let chat = app_state
+        .use_cases
+        .update_chat(Actor::try_from(&ctx)?, UpdateChatDTO::try_new(id, payload)?)
+        .await?;
+
+    Ok(Json(ChatPresenter::for_viewer(&viewer, chat)))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TransferOwnershipPayload {
+    pub user_id: Uuid,
+}
+
+impl Validate for TransferOwnershipPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        // The nil UUID parses fine but never names an account
+        if self.user_id.is_nil() {
+            return Err(ValidationErrors::single("user_id", "invalid_uuid", "user_id must name a user"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Hand a chat over to another user, by its owner or an admin; the previous
+/// owner stays on as a member
+#[utoipa::path(
+    put,
+    path = "/api/v1/chats/{id}/owner",
+    tag = CHAT_TAG,
+    params(("id" = Uuid, Path, description = "Chat id")),
+    request_body = TransferOwnershipPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = ChatPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown chat or user", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`, also for anyone but the owner or an admin", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn transfer_ownership(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    viewer: ChatViewer,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<TransferOwnershipPayload>,
+) -> AppResult<Json<ChatPresenter>> {
+    let transfer = TransferOwnershipDTO { chat_id: id, user_id: payload.user_id };
+
+    let chat = app_state.use_cases.transfer_chat_ownership(Actor::try_from(&ctx)?, transfer).await?;
+
+    Ok(Json(ChatPresenter::for_viewer(&viewer, chat)))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NearbyChatsQuery {
+    /// Latitude in degrees
+    pub lat: f64,
+    /// Longitude in degrees
+    pub lon: f64,
+    /// Search radius in metres, up to 50 km (default 1 km)
+    pub radius: Option<f64>,
+    /// At most 200 (default 50)
+    pub limit: Option<u32>,
+}
+
+impl Validate for NearbyChatsQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.coordinates(Some(self.lat), Some(self.lon));
+
+        if let Some(radius) = self.radius {
+            errors.between("radius", radius, 1.0, MAX_NEARBY_RADIUS_M);
+        }
+
+        if let Some(limit) = self.limit {
+            errors.range("limit", limit, 1, MAX_NEARBY_LIMIT);
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Chats with coordinates around a point, closest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/chats/nearby",
+    tag = CHAT_TAG,
+    params(NearbyChatsQuery),
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<NearbyChatPresenter>),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn get_nearby_chats(
+    State(app_state): State<AppState>,
+    viewer: ChatViewer,
+    ValidatedQuery(query): ValidatedQuery<NearbyChatsQuery>,
+) -> AppResult<Json<Vec<NearbyChatPresenter>>> {
+    let chats = app_state.use_cases.find_nearby_chats(query.try_into()?).await?;
+
+    Ok(Json(chats.into_iter().map(|c| NearbyChatPresenter::for_viewer(&viewer, c)).collect()))
+}
+
+/// Join a chat as a member; joining again changes nothing
+#[utoipa::path(
+    post,
+    path = "/api/v1/chats/{id}/join",
+    tag = CHAT_TAG,
+    params(("id" = Uuid, Path, description = "Chat id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = ChatPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown chat", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn join_chat(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    viewer: ChatViewer,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Json<ChatPresenter>> {
+    let chat = app_state.use_cases.join_chat(id, ctx.get_user_id()).await?;
+
+    Ok(Json(ChatPresenter::for_viewer(&viewer, chat)))
+}
+
+/// Leave a chat; its owner has to hand it over first
+#[utoipa::path(
+    post,
+    path = "/api/v1/chats/{id}/leave",
+    tag = CHAT_TAG,
+    params(("id" = Uuid, Path, description = "Chat id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = StatusMessage),
+        (status = 400, description = "`INVALID_PARAMS`: unknown chat, or not a member", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 409, description = "`OWNER_CANNOT_LEAVE`", body = ErrorResponse),
+    )
+)]
+async fn leave_chat(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Json<Value>> {
+    app_state.use_cases.leave_chat(id, ctx.get_user_id()).await?;
+
+    Ok(Json(json!({
+        "message": "left chat",
+        "status": "ok",
+    })))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MembersQuery {
+    /// Page size, 1 to 100 (default 20)
+    pub limit: Option<u32>,
+    /// `X-Next-Cursor` of the previous page
+    pub cursor: Option<String>,
+}
+
+impl Validate for MembersQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(limit) = self.limit {
+            errors.range("limit", limit, 1, MAX_PAGE_SIZE);
+        }
+
+        if let Some(cursor) = &self.cursor
+            && let Err(e) = Cursor::decode(cursor, ListMembersDTO::SORT)
+        {
+            errors.extend(e);
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Members of a chat in the order they joined
+#[utoipa::path(
+    get,
+    path = "/api/v1/chats/{id}/members",
+    tag = CHAT_TAG,
+    params(("id" = Uuid, Path, description = "Chat id"), MembersQuery),
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<ChatMemberPresenter>, headers(
+            ("x-total-count" = i64, description = "Members across all pages"),
+            ("x-next-cursor" = String, description = "Pass as `cursor` for the next page; absent on the last page"),
+        )),
+        (status = 400, description = "`INVALID_PARAMS`: unknown chat", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn get_chat_members(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedQuery(query): ValidatedQuery<MembersQuery>,
+) -> AppResult<(HeaderMap, Json<Vec<ChatMemberPresenter>>)> {
+    let page = app_state.use_cases.list_chat_members(ListMembersDTO::try_new(id, query)?).await?;
+
+    Ok(paginated(page.map(ChatMemberPresenter::from)))
+}
+
+#[derive(Deserialize, ToSchema)]
 pub struct DeleteChatPayload {
     id: String,
 }
 
+impl Validate for DeleteChatPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.uuid("id", &self.id);
+
+        errors.into_result()
+    }
+}
+
+/// Remove a chat (admin only); restorable until the retention window ends
+#[utoipa::path(
+    delete,
+    path = "/api/v1/chats/{id}",
+    tag = CHAT_TAG,
+    params(("id" = Uuid, Path, description = "Chat id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = StatusMessage),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
 async fn delete_chat(
     State(app_state): State<AppState>,
-    Json(payload): Json<DeleteChatPayload>
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Json<Value>> {
+    app_state.use_cases.delete_chat_by_id(id.to_string()).await?;
+
+    Ok(
+        Json(
+            json!(
+                {
+                    "message": "chat deleted",
+                    "status": "ok",
+                }
+            )
+        )
+    )
+}
+
+/// Chats deleted within the retention window (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/chats/deleted",
+    tag = CHAT_TAG,
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<DeletedChatPresenter>),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn get_deleted_chats(
+    State(app_state): State<AppState>
+) -> AppResult<Json<Vec<DeletedChatPresenter>>> {
+    let retention = app_state.use_cases.retention();
+
+    let chats = app_state.use_cases.get_deleted_chats().await?;
+
+    Ok(Json(chats.into_iter().map(|c| DeletedChatPresenter::new(c, retention)).collect()))
+}
+
+/// Undo a deletion within the retention window (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/chats/{id}/restore",
+    tag = CHAT_TAG,
+    params(("id" = Uuid, Path, description = "Chat id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = ChatPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: not deleted, or already purged", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 409, description = "`CHAT_NAME_TAKEN` by a chat created since", body = ErrorResponse),
+    )
+)]
+async fn restore_chat(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Json<ChatPresenter>> {
+    let chat = app_state.use_cases.restore_chat(id.to_string()).await?;
+
+    Ok(Json(chat.into()))
+}
+
+/// Follow a chat's invite link; every follow is counted per day. Requires a
+/// session when the server hides invite links
+#[utoipa::path(
+    get,
+    path = "/c/{id}",
+    tag = CHAT_TAG,
+    params(("id" = Uuid, Path, description = "Chat id")),
+    security((), ("cookie_auth" = [])),
+    responses(
+        (status = 307, description = "Redirect to the invite link", headers(("location" = String))),
+        (status = 400, description = "`INVALID_PARAMS`: unknown chat, or it has no invite link", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH` while invite links are hidden", body = ErrorResponse),
+    )
+)]
+async fn follow_invite_link(
+    State(app_state): State<AppState>,
+    ctx: AppResult<Ctx>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Response> {
+    if app_state.hide_invite_links {
+        ctx?;
+    }
+
+    let link = app_state.use_cases.follow_invite_link(id).await?;
+
+    // Every follow has to reach the server to be counted
+    Ok(([(header::CACHE_CONTROL, "no-store")], Redirect::temporary(link.as_str())).into_response())
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LinkReportQuery {
+    /// Days to count back including today, up to 365 (default 30)
+    pub days: Option<u32>,
+    /// At most 100 (default 20)
+    pub limit: Option<u32>,
+}
+
+impl Validate for LinkReportQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(days) = self.days {
+            errors.range("days", days, 1, MAX_LINK_REPORT_DAYS);
+        }
+
+        if let Some(limit) = self.limit {
+            errors.range("limit", limit, 1, MAX_LINK_REPORT_LIMIT);
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Chats whose invite links were followed most, by UTC day (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/chats/most-followed",
+    tag = CHAT_TAG,
+    params(LinkReportQuery),
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<FollowedChatPresenter>),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn get_most_followed_chats(
+    State(app_state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<LinkReportQuery>,
+) -> AppResult<Json<Vec<FollowedChatPresenter>>> {
+    let chats = app_state.use_cases.get_most_followed_chats(LinkReportDTO::from(query)).await?;
+
+    Ok(Json(chats.into_iter().map(FollowedChatPresenter::from).collect()))
+}
+
+/// Chats whose invite links failed several checks in a row (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/chats/dead-links",
+    tag = CHAT_TAG,
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<DeadLinkPresenter>),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn get_dead_links(
+    State(app_state): State<AppState>,
+) -> AppResult<Json<Vec<DeadLinkPresenter>>> {
+    let dead = app_state.link_monitor.get_dead_links().await?;
+
+    Ok(Json(dead.into_iter().map(DeadLinkPresenter::from).collect()))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LinkChecksQuery {
+    /// 1 to 100 (default 20)
+    pub limit: Option<u32>,
+}
+
+impl Validate for LinkChecksQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(limit) = self.limit {
+            errors.range("limit", limit, 1, MAX_PAGE_SIZE);
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Latest checks of a chat's invite link, newest first (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/chats/{id}/link-checks",
+    tag = CHAT_TAG,
+    params(("id" = Uuid, Path, description = "Chat id"), LinkChecksQuery),
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<LinkCheckPresenter>),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn get_link_checks(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedQuery(query): ValidatedQuery<LinkChecksQuery>,
+) -> AppResult<Json<Vec<LinkCheckPresenter>>> {
+    let checks = app_state
+        .link_monitor
+        .get_link_checks(id, query.limit.unwrap_or(DEFAULT_PAGE_SIZE))
+        .await?;
+
+    Ok(Json(checks.into_iter().map(LinkCheckPresenter::from).collect()))
+}
+
+async fn legacy_delete_chat(
+    State(app_state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<DeleteChatPayload>
 ) -> AppResult<Json<Value>> {
 
     app_state.use_cases.delete_chat_by_id(payload.id).await?;
@@ -75,4 +767,4 @@ async fn delete_chat(
             )
         )
     )
-}
\ No newline at end of file
+}
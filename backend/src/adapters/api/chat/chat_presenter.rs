@@ -1,25 +1,293 @@
+use std::collections::HashSet;
+
+use axum::{extract::FromRequestParts, http::request::Parts};
+use chrono::TimeDelta;
 use serde::Serialize;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::domain::entities::chat::Chat;
+use crate::{adapters::{api::app_state::AppState, ctx::Ctx}, application::{AppError, AppResult, dto::{chat::{ChatFacetsDTO, FollowedChatDTO, MemberDTO, NearbyChatDTO}, link_check::DeadLinkDTO}, retention::Deleted}, domain::entities::{chat::Chat, link_check::LinkCheck, user::Actor}};
+
+/// Who asks for a chat, deciding which of its details they get.
+pub struct ChatViewer {
+    actor: Actor,
+    hide_invite_links: bool,
+    favorites: HashSet<Uuid>,
+}
+
+impl ChatViewer {
+    pub fn new(ctx: &Ctx, hide_invite_links: bool) -> AppResult<Self> {
+        Ok(Self { actor: Actor::try_from(ctx)?, hide_invite_links, favorites: HashSet::new() })
+    }
+
+    pub fn with_favorites(self, favorites: HashSet<Uuid>) -> Self {
+        Self { favorites, ..self }
+    }
+
+    fn sees_invite_link(&self, chat: &Chat) -> bool {
+        !self.hide_invite_links || self.actor.is_admin() || chat.owner_id == Some(self.actor.user_id)
+    }
+}
 
-#[derive(Debug, Serialize)]
+impl FromRequestParts<AppState> for ChatViewer {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> AppResult<Self> {
+        let ctx = Ctx::from_request_parts(parts, state).await?;
+
+        let viewer = Self::new(&ctx, state.hide_invite_links)?;
+        let favorites = state.use_cases.get_favorite_chat_ids(viewer.actor.user_id).await?;
+
+        Ok(viewer.with_favorites(favorites))
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ChatPresenter {
     pub id: Uuid,
     pub name: String,
     pub description: String,
     pub users_count: u64,
     pub location: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub location_id: Option<Uuid>,
+    pub category: String,
+    pub tags: Vec<String>,
+    pub owner_id: Option<Uuid>,
+    /// Withheld from everyone but the owner and admins when links are hidden
+    pub invite_link: Option<String>,
+    /// `/c/{id}`, which counts the follow and redirects to the invite link
+    pub invite_url: Option<String>,
+    /// Whether the caller has the chat among their favourites
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_favorite: Option<bool>,
+}
+
+impl ChatPresenter {
+    pub fn for_viewer(viewer: &ChatViewer, chat: Chat) -> Self {
+        let sees_invite_link = viewer.sees_invite_link(&chat);
+        let is_favorite = viewer.favorites.contains(&chat.id);
+
+        let mut presenter = Self { is_favorite: Some(is_favorite), ..Self::from(chat) };
+
+        if !sees_invite_link {
+            presenter.invite_link = None;
+        }
+
+        presenter
+    }
 }
 
 impl From<Chat> for ChatPresenter {
     fn from(value: Chat) -> Self {
         Self { 
             id: value.id, 
-            name: value.name, 
+            name: value.name.to_string(), 
             description: value.description, 
-            users_count: value.users_count, 
-            location: value.location,
+            users_count: value.users_count.get(), 
+            location: value.location.to_string(),
+            latitude: value.position.map(|p| p.lat()),
+            longitude: value.position.map(|p| p.lon()),
+            location_id: value.location_id,
+            category: value.category.as_str().to_string(),
+            tags: value.tags.iter().map(|t| t.to_string()).collect(),
+            owner_id: value.owner_id,
+            invite_url: value.invite_link.as_ref().map(|_| format!("/c/{}", value.id)),
+            invite_link: value.invite_link.map(|l| l.as_str().to_string()),
+            is_favorite: None,
          }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NearbyChatPresenter {
+    #[serde(flatten)]
+    pub chat: ChatPresenter,
+    /// Metres from the requested point
+    pub distance_m: f64,
+}
+
+impl NearbyChatPresenter {
+    pub fn for_viewer(viewer: &ChatViewer, nearby: NearbyChatDTO) -> Self {
+        Self {
+            chat: ChatPresenter::for_viewer(viewer, nearby.chat),
+            distance_m: nearby.distance_m.round(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeletedChatPresenter {
+    #[serde(flatten)]
+    pub chat: ChatPresenter,
+    pub deleted_at: String,
+    /// Purged after this moment
+    pub restorable_until: String,
+}
+
+impl DeletedChatPresenter {
+    pub fn new(deleted: Deleted<Chat>, retention: TimeDelta) -> Self {
+        Self {
+            deleted_at: deleted.deleted_at.to_rfc3339(),
+            restorable_until: deleted.restorable_until(retention).to_rfc3339(),
+            chat: deleted.item.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: i64,
+}
+
+/// Most frequent first.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ChatFacetsPresenter {
+    pub categories: Vec<FacetCount>,
+    pub tags: Vec<FacetCount>,
+}
+
+impl From<ChatFacetsDTO> for ChatFacetsPresenter {
+    fn from(value: ChatFacetsDTO) -> Self {
+        Self {
+            categories: value
+                .categories
+                .into_iter()
+                .map(|(category, count)| FacetCount { value: category.as_str().to_string(), count })
+                .collect(),
+            tags: value
+                .tags
+                .into_iter()
+                .map(|(tag, count)| FacetCount { value: tag.to_string(), count })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ChatMemberPresenter {
+    pub user_id: Uuid,
+    pub display_name: String,
+    /// `owner`, `moderator` or `member`
+    pub role: String,
+    pub joined_at: String,
+}
+
+impl From<MemberDTO> for ChatMemberPresenter {
+    fn from(value: MemberDTO) -> Self {
+        Self {
+            user_id: value.user_id,
+            display_name: value.display_name,
+            role: value.role.as_str().to_string(),
+            joined_at: value.joined_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FollowedChatPresenter {
+    pub chat_id: Uuid,
+    pub name: String,
+    /// Follows of `/c/{id}` within the reported days
+    pub clicks: i64,
+    /// UTC day of the latest follow
+    pub last_followed_on: String,
+}
+
+impl From<FollowedChatDTO> for FollowedChatPresenter {
+    fn from(value: FollowedChatDTO) -> Self {
+        Self {
+            chat_id: value.chat_id,
+            name: value.name,
+            clicks: value.clicks,
+            last_followed_on: value.last_followed_on.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LinkCheckPresenter {
+    /// The link as it was when checked
+    pub invite_link: String,
+    /// `ok`, `broken` or `unreachable`
+    pub outcome: String,
+    /// HTTP status, unless unreachable
+    pub status: Option<u16>,
+    /// Why there was no answer, if unreachable
+    pub error: Option<String>,
+    pub checked_at: String,
+}
+
+impl From<LinkCheck> for LinkCheckPresenter {
+    fn from(value: LinkCheck) -> Self {
+        Self {
+            invite_link: value.link.as_str().to_string(),
+            outcome: value.outcome.kind().as_str().to_string(),
+            status: value.outcome.status(),
+            error: value.outcome.error().map(str::to_string),
+            checked_at: value.checked_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeadLinkPresenter {
+    pub chat_id: Uuid,
+    pub name: String,
+    /// Failed checks in a row
+    pub failures: u32,
+    pub last_check: LinkCheckPresenter,
+}
+
+impl From<DeadLinkDTO> for DeadLinkPresenter {
+    fn from(value: DeadLinkDTO) -> Self {
+        Self {
+            chat_id: value.chat_id,
+            name: value.name,
+            failures: value.health.failures,
+            last_check: value.last_check.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::entities::value_objects::{ChatName, InviteLink, Location, MemberCount};
+
+    use super::*;
+
+    #[test]
+    fn test_hidden_invite_link_reaches_owner_and_admin_only() {
+        let (chat_id, owner_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let chat = || {
+            Chat::new(Some(chat_id), ChatName::parse("Building 5").unwrap(), String::new(), MemberCount::default(), Location::parse("Main st. 5").unwrap())
+                .with_owner_id(Some(owner_id))
+                .with_invite_link(Some(InviteLink::parse("https://t.me/building5").unwrap()))
+        };
+
+        let sees = |user_id: Uuid, role: &str, hide_invite_links| {
+            let viewer = ChatViewer::new(&Ctx::new(user_id.to_string(), role.to_string()), hide_invite_links).unwrap();
+
+            ChatPresenter::for_viewer(&viewer, chat()).invite_link.is_some()
+        };
+
+        assert!(sees(Uuid::new_v4(), "user", false));
+        assert!(sees(owner_id, "user", true));
+        assert!(sees(Uuid::new_v4(), "admin", true));
+        assert!(!sees(Uuid::new_v4(), "user", true));
+
+        assert_eq!(ChatPresenter::from(chat()).invite_url, Some(format!("/c/{chat_id}")));
+    }
+
+    #[test]
+    fn test_favorite_flag_follows_viewer() {
+        let chat = Chat::new(None, ChatName::parse("Building 5").unwrap(), String::new(), MemberCount::default(), Location::parse("Main st. 5").unwrap());
+        let viewer = ChatViewer::new(&Ctx::new(Uuid::new_v4().to_string(), "user".to_string()), false).unwrap();
+
+        let favorite = ChatPresenter::for_viewer(&viewer.with_favorites(HashSet::from([chat.id])), chat);
+
+        assert_eq!(favorite.is_favorite, Some(true));
+    }
+}
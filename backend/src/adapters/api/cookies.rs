@@ -0,0 +1,53 @@
+use tower_cookies::{
+    Cookie,
+    cookie::{SameSite, time::Duration},
+};
+
+use crate::adapters::{
+    api::{AUTH_TOKEN, CSRF_TOKEN},
+    crypto::token::TOKEN_TTL_SECS,
+};
+
+/// Attributes shared by every cookie the API sets, so that the auth and CSRF
+/// cookies are always issued and removed with matching scope.
+#[derive(Debug, Clone)]
+pub struct CookieSettings {
+    pub secure: bool,
+    pub same_site: SameSite,
+    pub domain: Option<String>,
+}
+
+impl CookieSettings {
+    fn build(&self, name: &'static str, value: String) -> Cookie<'static> {
+        let mut cookie = Cookie::new(name, value);
+
+        cookie.set_path("/");
+        cookie.set_secure(self.secure);
+        cookie.set_same_site(self.same_site);
+        cookie.set_max_age(Duration::seconds(TOKEN_TTL_SECS));
+
+        if let Some(domain) = &self.domain {
+            cookie.set_domain(domain.clone());
+        }
+
+        cookie
+    }
+
+    pub fn auth_cookie(&self, token: String) -> Cookie<'static> {
+        let mut cookie = self.build(AUTH_TOKEN, token);
+
+        cookie.set_http_only(true);
+
+        cookie
+    }
+
+    /// Readable by the frontend, which echoes it back in the CSRF header.
+    pub fn csrf_cookie(&self, csrf_token: String) -> Cookie<'static> {
+        self.build(CSRF_TOKEN, csrf_token)
+    }
+
+    /// Cookie with the same scope as the issued one, for `Cookies::remove`.
+    pub fn removal_cookie(&self, name: &'static str) -> Cookie<'static> {
+        self.build(name, String::new())
+    }
+}
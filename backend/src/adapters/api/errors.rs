@@ -1,11 +1,114 @@
+use axum::{Json, http::StatusCode};
 use serde::Serialize;
+use serde_json::{Value, json};
+use utoipa::ToSchema;
 
+use crate::adapters::api::{i18n::{self, Lang}, openapi::META_TAG};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
 #[allow(non_camel_case_types)]
 pub enum ClientError {
     LOGIN_FAIL,
     NO_AUTH,
     INVALID_PARAMS,
+    CSRF_FAIL,
+    VALIDATION_ERROR,
+    EMAIL_TAKEN,
+    CHAT_NAME_TAKEN,
+    LOCATION_NAME_TAKEN,
+    LOCATION_IN_USE,
+    TAG_NAME_TAKEN,
+    OWNER_CANNOT_LEAVE,
+    SUBMISSION_DECIDED,
+    PASSWORD_MISMATCH,
     SERVICE_ERROR,
 }
+
+impl ClientError {
+    pub const ALL: [ClientError; 14] = [
+        ClientError::LOGIN_FAIL,
+        ClientError::NO_AUTH,
+        ClientError::INVALID_PARAMS,
+        ClientError::CSRF_FAIL,
+        ClientError::VALIDATION_ERROR,
+        ClientError::EMAIL_TAKEN,
+        ClientError::CHAT_NAME_TAKEN,
+        ClientError::LOCATION_NAME_TAKEN,
+        ClientError::LOCATION_IN_USE,
+        ClientError::TAG_NAME_TAKEN,
+        ClientError::OWNER_CANNOT_LEAVE,
+        ClientError::SUBMISSION_DECIDED,
+        ClientError::PASSWORD_MISMATCH,
+        ClientError::SERVICE_ERROR,
+    ];
+
+    /// Stable identifier clients can switch on; never changes once released.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ClientError::LOGIN_FAIL => "LOGIN_FAIL",
+            ClientError::NO_AUTH => "NO_AUTH",
+            ClientError::INVALID_PARAMS => "INVALID_PARAMS",
+            ClientError::CSRF_FAIL => "CSRF_FAIL",
+            ClientError::VALIDATION_ERROR => "VALIDATION_ERROR",
+            ClientError::EMAIL_TAKEN => "EMAIL_TAKEN",
+            ClientError::CHAT_NAME_TAKEN => "CHAT_NAME_TAKEN",
+            ClientError::LOCATION_NAME_TAKEN => "LOCATION_NAME_TAKEN",
+            ClientError::LOCATION_IN_USE => "LOCATION_IN_USE",
+            ClientError::TAG_NAME_TAKEN => "TAG_NAME_TAKEN",
+            ClientError::OWNER_CANNOT_LEAVE => "OWNER_CANNOT_LEAVE",
+            ClientError::SUBMISSION_DECIDED => "SUBMISSION_DECIDED",
+            ClientError::PASSWORD_MISMATCH => "PASSWORD_MISMATCH",
+            ClientError::SERVICE_ERROR => "SERVICE_ERROR",
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ClientError::LOGIN_FAIL
+            | ClientError::NO_AUTH
+            | ClientError::CSRF_FAIL
+            | ClientError::PASSWORD_MISMATCH => StatusCode::FORBIDDEN,
+            ClientError::INVALID_PARAMS => StatusCode::BAD_REQUEST,
+            ClientError::VALIDATION_ERROR => StatusCode::UNPROCESSABLE_ENTITY,
+            ClientError::EMAIL_TAKEN
+            | ClientError::CHAT_NAME_TAKEN
+            | ClientError::LOCATION_NAME_TAKEN
+            | ClientError::LOCATION_IN_USE
+            | ClientError::TAG_NAME_TAKEN
+            | ClientError::OWNER_CANNOT_LEAVE
+            | ClientError::SUBMISSION_DECIDED => StatusCode::CONFLICT,
+            ClientError::SERVICE_ERROR => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn message(&self, lang: Lang) -> String {
+        i18n::message(self.code(), lang)
+    }
+}
+
+/// Every error code with its status and translations
+#[utoipa::path(
+    get,
+    path = "/api/errors",
+    tag = META_TAG,
+    responses((status = 200, description = "`{ errors: [{ code, status, messages: { en, ru } }] }`"))
+)]
+pub async fn list_error_codes() -> Json<Value> {
+    let errors: Vec<Value> = ClientError::ALL
+        .iter()
+        .map(|e| {
+            let messages: serde_json::Map<String, Value> = Lang::ALL
+                .iter()
+                .map(|l| (l.as_str().to_string(), Value::String(e.message(*l))))
+                .collect();
+
+            json!({
+                "code": e.code(),
+                "status": e.status().as_u16(),
+                "messages": messages,
+            })
+        })
+        .collect();
+
+    Json(json!({ "errors": errors }))
+}
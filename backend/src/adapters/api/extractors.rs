@@ -0,0 +1,161 @@
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, FromRequestParts, Path, Query, Request, rejection::PathRejection},
+    http::{header, request::Parts},
+};
+use serde::de::DeserializeOwned;
+
+use crate::application::{
+    AppError,
+    validation::{Validate, ValidationErrors},
+};
+
+/// `Json` replacement that reports malformed bodies and failed field rules in
+/// the common 422 shape instead of axum's plain-text rejections.
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let is_json = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("application/json") || v.contains("+json"));
+
+        if !is_json {
+            return Err(AppError::Validation(ValidationErrors::single(
+                "body",
+                "unsupported_content_type",
+                "expected an application/json body",
+            )));
+        }
+
+        let bytes = Bytes::from_request(req, state).await.map_err(|e| {
+            AppError::Validation(ValidationErrors::single("body", "unreadable_body", e.body_text()))
+        })?;
+
+        let payload = deserialize::<T>(&bytes).map_err(AppError::Validation)?;
+
+        payload.validate().map_err(AppError::Validation)?;
+
+        Ok(Self(payload))
+    }
+}
+
+/// `Path` replacement reporting unparsable segments (e.g. a malformed UUID)
+/// as field errors named after the route parameter.
+pub struct ValidatedPath<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidatedPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match Path::<T>::from_request_parts(parts, state).await {
+            Ok(Path(value)) => Ok(Self(value)),
+            Err(PathRejection::FailedToDeserializePathParams(e)) => {
+                let field = match e.kind() {
+                    axum::extract::path::ErrorKind::ParseErrorAtKey { key, .. }
+                    | axum::extract::path::ErrorKind::InvalidUtf8InPathParam { key } => key.clone(),
+                    // A single unnamed parameter, which is always `{id}` in this API
+                    _ => "id".to_string(),
+                };
+
+                Err(AppError::Validation(ValidationErrors::single(&field, "invalid_path_param", e.body_text())))
+            }
+            Err(_) => Err(AppError::Internal),
+        }
+    }
+}
+
+/// `Query` counterpart of `ValidatedJson`.
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(query) = Query::<T>::from_request_parts(parts, state).await.map_err(|e| {
+            AppError::Validation(ValidationErrors::single("query", "invalid_query", e.body_text()))
+        })?;
+
+        query.validate().map_err(AppError::Validation)?;
+
+        Ok(Self(query))
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, ValidationErrors> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(bytes);
+
+    serde_path_to_error::deserialize(deserializer).map_err(|err| {
+        let path = err.path().to_string();
+        let inner = err.inner();
+
+        let message = inner.to_string();
+
+        // serde reports a missing field against its parent, the name is only in the message
+        if let Some(field) = message
+            .strip_prefix("missing field `")
+            .and_then(|rest| rest.split('`').next())
+        {
+            let field = match path.as_str() {
+                "." => field.to_string(),
+                parent => format!("{parent}.{field}"),
+            };
+
+            return ValidationErrors::single(&field, "required", format!("{field} is required"));
+        }
+
+        let field = if path == "." { "body" } else { path.as_str() };
+
+        let code = if inner.is_data() { "invalid_type" } else { "invalid_json" };
+
+        ValidationErrors::single(field, code, message)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Payload {
+        name: String,
+        users_count: i64,
+    }
+
+    #[test]
+    fn test_missing_field_is_reported_by_name() {
+        let errors = deserialize::<Payload>(br#"{"users_count": 3}"#).unwrap_err();
+
+        assert_eq!(errors.errors()[0].field, "name");
+        assert_eq!(errors.errors()[0].code, "required");
+    }
+
+    #[test]
+    fn test_wrong_type_and_syntax() {
+        let wrong_type = deserialize::<Payload>(br#"{"name": "a", "users_count": "many"}"#).unwrap_err();
+        let bad_syntax = deserialize::<Payload>(br#"{"name": "#).unwrap_err();
+
+        assert_eq!(wrong_type.errors()[0].field, "users_count");
+        assert_eq!(wrong_type.errors()[0].code, "invalid_type");
+        assert_eq!(bad_syntax.errors()[0].code, "invalid_json");
+    }
+}
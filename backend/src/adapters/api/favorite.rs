@@ -0,0 +1 @@
+pub mod favorite_controller;
@@ -0,0 +1,110 @@
+use axum::{Json, extract::State, middleware, routing::{get, post}};
+use serde_json::{Value, json};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+use crate::{
+    adapters::{
+        api::{
+            app_state::AppState,
+            chat::chat_presenter::{ChatPresenter, ChatViewer},
+            extractors::ValidatedPath,
+            middlewares,
+            openapi::{ErrorResponse, StatusMessage, USER_TAG},
+        },
+        ctx::Ctx,
+    },
+    application::AppResult,
+};
+
+pub fn favorite_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(get_favorites))
+        .routes(routes!(add_favorite, remove_favorite))
+        .route_layer(middleware::from_fn(middlewares::require_auth))
+}
+
+/// Favourite routes under the paths they were introduced with; these are new,
+/// so not deprecated like the rest of `/api/user`.
+pub fn user_favorite_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .route("/api/user/favorites", get(get_favorites))
+        .route("/api/user/favorites/{chat_id}", post(add_favorite).delete(remove_favorite))
+        .route_layer(middleware::from_fn(middlewares::require_auth))
+}
+
+/// The caller's favourite chats, most recently added first
+///
+/// Also served at `/api/user/favorites`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/favorites",
+    tag = USER_TAG,
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<ChatPresenter>),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn get_favorites(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    viewer: ChatViewer,
+) -> AppResult<Json<Vec<ChatPresenter>>> {
+    let chats = app_state.use_cases.get_favorite_chats(ctx.get_user_id()).await?;
+
+    Ok(Json(chats.into_iter().map(|c| ChatPresenter::for_viewer(&viewer, c)).collect()))
+}
+
+/// Add a chat to the caller's favourites; adding it again changes nothing
+///
+/// Also served at `/api/user/favorites/{chat_id}`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/me/favorites/{chat_id}",
+    tag = USER_TAG,
+    params(("chat_id" = Uuid, Path, description = "Chat id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = ChatPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown chat", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn add_favorite(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    viewer: ChatViewer,
+    ValidatedPath(chat_id): ValidatedPath<Uuid>,
+) -> AppResult<Json<ChatPresenter>> {
+    let chat = app_state.use_cases.add_favorite(ctx.get_user_id(), chat_id).await?;
+
+    Ok(Json(ChatPresenter { is_favorite: Some(true), ..ChatPresenter::for_viewer(&viewer, chat) }))
+}
+
+/// Remove a chat from the caller's favourites
+///
+/// Also served at `/api/user/favorites/{chat_id}`.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/me/favorites/{chat_id}",
+    tag = USER_TAG,
+    params(("chat_id" = Uuid, Path, description = "Chat id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = StatusMessage),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn remove_favorite(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    ValidatedPath(chat_id): ValidatedPath<Uuid>,
+) -> AppResult<Json<Value>> {
+    app_state.use_cases.remove_favorite(ctx.get_user_id(), chat_id).await?;
+
+    Ok(Json(json!({
+        "message": "favorite removed",
+        "status": "ok",
+    })))
+}
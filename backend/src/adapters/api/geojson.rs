@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::entities::{boundary::Boundary, errors::DomainError, value_objects::GeoPoint};
+
+/// GeoJSON geometries used by the map (RFC 7946). Positions are
+/// `[longitude, latitude]`; a trailing altitude is accepted and dropped.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type")]
+pub enum Geometry {
+    Point { coordinates: Vec<f64> },
+    Polygon { coordinates: Vec<Vec<Vec<f64>>> },
+    MultiPolygon { coordinates: Vec<Vec<Vec<Vec<f64>>>> },
+}
+
+fn position(point: &GeoPoint) -> Vec<f64> {
+    vec![point.lon(), point.lat()]
+}
+
+fn point(position: &[f64]) -> Result<GeoPoint, DomainError> {
+    match position {
+        [lon, lat, ..] => GeoPoint::new(*lat, *lon),
+        _ => Err(DomainError::BoundaryInvalid("a position needs longitude and latitude".to_string())),
+    }
+}
+
+fn polygon(rings: &[Vec<Vec<f64>>]) -> Result<Vec<Vec<GeoPoint>>, DomainError> {
+    rings
+        .iter()
+        .map(|ring| ring.iter().map(|p| point(p)).collect())
+        .collect()
+}
+
+impl From<GeoPoint> for Geometry {
+    fn from(value: GeoPoint) -> Self {
+        Geometry::Point { coordinates: position(&value) }
+    }
+}
+
+impl From<&Boundary> for Geometry {
+    fn from(value: &Boundary) -> Self {
+        let mut polygons: Vec<Vec<Vec<Vec<f64>>>> = value
+            .polygons()
+            .iter()
+            .map(|rings| rings.iter().map(|ring| ring.iter().map(position).collect()).collect())
+            .collect();
+
+        match polygons.len() {
+            1 => Geometry::Polygon { coordinates: polygons.remove(0) },
+            _ => Geometry::MultiPolygon { coordinates: polygons },
+        }
+    }
+}
+
+impl TryFrom<Geometry> for Boundary {
+    type Error = DomainError;
+
+    fn try_from(value: Geometry) -> Result<Self, Self::Error> {
+        let polygons = match value {
+            Geometry::Point { .. } => {
+                return Err(DomainError::BoundaryInvalid("a point does not cover an area".to_string()));
+            }
+            Geometry::Polygon { coordinates } => vec![polygon(&coordinates)?],
+            Geometry::MultiPolygon { coordinates } => coordinates
+                .iter()
+                .map(|rings| polygon(rings))
+                .collect::<Result<_, _>>()?,
+        };
+
+        Boundary::new(polygons)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polygon_round_trips_through_boundary() {
+        let json = r#"{"type":"Polygon","coordinates":[[[30.0,59.0,12.5],[31.0,59.0],[31.0,60.0],[30.0,60.0],[30.0,59.0,12.5]]]}"#;
+
+        let geometry: Geometry = serde_json::from_str(json).unwrap();
+        let boundary = Boundary::try_from(geometry).unwrap();
+
+        assert!(boundary.contains(&GeoPoint::new(59.5, 30.5).unwrap()));
+
+        let Geometry::Polygon { coordinates } = Geometry::from(&boundary) else {
+            panic!("single polygon should stay a Polygon");
+        };
+
+        assert_eq!(coordinates[0][1], vec![31.0, 59.0]);
+    }
+}
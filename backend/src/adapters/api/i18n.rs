@@ -0,0 +1,120 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use axum::http::{HeaderMap, header};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    En,
+    Ru,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::En, Lang::Ru];
+
+    pub const DEFAULT: Lang = Lang::En;
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Ru => "ru",
+        }
+    }
+
+    fn from_tag(tag: &str) -> Option<Self> {
+        // Only the primary subtag matters: `ru-RU` and `ru` share a catalogue
+        let primary = tag.split('-').next()?.trim().to_lowercase();
+
+        Lang::ALL.into_iter().find(|l| l.as_str() == primary)
+    }
+
+    /// Picks the supported language with the highest `q` from `Accept-Language`.
+    pub fn negotiate(headers: &HeaderMap) -> Self {
+        let Some(accept) = headers
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|h| h.to_str().ok())
+        else {
+            return Self::DEFAULT;
+        };
+
+        let mut best: Option<(Lang, f32)> = None;
+
+        for entry in accept.split(',') {
+            let mut parts = entry.split(';');
+
+            let Some(lang) = parts.next().and_then(Lang::from_tag) else {
+                continue;
+            };
+
+            let q = parts
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .and_then(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            if q > 0.0 && best.is_none_or(|(_, best_q)| q > best_q) {
+                best = Some((lang, q));
+            }
+        }
+
+        best.map(|(lang, _)| lang).unwrap_or(Self::DEFAULT)
+    }
+}
+
+type Catalogue = HashMap<String, String>;
+
+static CATALOGUES: LazyLock<HashMap<Lang, Catalogue>> = LazyLock::new(|| {
+    let parse = |raw: &str| serde_json::from_str::<Catalogue>(raw).expect("message catalogue is valid JSON");
+
+    HashMap::from([
+        (Lang::En, parse(include_str!("i18n/en.json"))),
+        (Lang::Ru, parse(include_str!("i18n/ru.json"))),
+    ])
+});
+
+/// Message for `code` in `lang`, falling back to English and then to the code itself.
+pub fn message(code: &str, lang: Lang) -> String {
+    [lang, Lang::DEFAULT]
+        .iter()
+        .find_map(|l| CATALOGUES.get(l).and_then(|c| c.get(code)))
+        .cloned()
+        .unwrap_or_else(|| code.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+    use crate::adapters::api::errors::ClientError;
+
+    fn negotiate(accept: &str) -> Lang {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_LANGUAGE, HeaderValue::from_str(accept).unwrap());
+
+        Lang::negotiate(&headers)
+    }
+
+    #[test]
+    fn test_negotiate_language() {
+        assert_eq!(negotiate("ru-RU,ru;q=0.9,en-US;q=0.8"), Lang::Ru);
+        assert_eq!(negotiate("de-DE,en;q=0.5,ru;q=0.7"), Lang::Ru);
+        assert_eq!(negotiate("de-DE"), Lang::En);
+        assert_eq!(negotiate("ru;q=0"), Lang::En);
+        assert_eq!(Lang::negotiate(&HeaderMap::new()), Lang::En);
+    }
+
+    #[test]
+    fn test_every_client_error_is_translated() {
+        for lang in Lang::ALL {
+            for error in ClientError::ALL {
+                assert!(
+                    CATALOGUES[&lang].contains_key(error.code()),
+                    "{} has no {} message",
+                    error.code(),
+                    lang.as_str()
+                );
+            }
+        }
+    }
+}
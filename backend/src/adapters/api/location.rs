@@ -0,0 +1,2 @@
+pub mod location_controller;
+pub mod location_presenter;
@@ -0,0 +1,319 @@
+use axum::{Json, extract::State, http::header, middleware};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use utoipa::{IntoParams, ToSchema};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+use crate::{
+    adapters::api::{
+        app_state::AppState,
+        chat::chat_presenter::ChatViewer,
+        extractors::{ValidatedJson, ValidatedPath, ValidatedQuery},
+        geojson::Geometry,
+        location::location_presenter::{LocationPresenter, LocationTreePresenter, MapFeature, MapPresenter, ResolvedDistrictPresenter},
+        middlewares,
+        openapi::{ErrorResponse, LOCATION_TAG, StatusMessage},
+    },
+    application::{AppResult, dto::location::{SetBoundaryDTO, UpdateLocationDTO}, validation::{Validate, ValidationErrors}},
+    domain::entities::{location::LocationKind, value_objects::{GeoPoint, PlaceName}},
+};
+
+pub fn location_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(create_location))
+        .routes(routes!(update_location, delete_location))
+        .routes(routes!(set_boundary, clear_boundary))
+        .route_layer(middleware::from_fn(middlewares::require_admin))
+        .routes(routes!(get_locations))
+        .routes(routes!(resolve_district))
+        .routes(routes!(get_map))
+        .route_layer(middleware::from_fn(middlewares::require_auth))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateLocationPayload {
+    /// Required for everything but cities
+    pub parent_id: Option<Uuid>,
+    /// `city`, `district`, `street` or `building`
+    pub kind: String,
+    pub name: String,
+}
+
+impl Validate for CreateLocationPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.parse::<LocationKind>("kind", &self.kind);
+
+        if errors.required("name", &self.name) {
+            errors.max_len("name", &self.name, PlaceName::MAX_LEN);
+        }
+
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateLocationPayload {
+    /// Moves the location, with everything below it, under another parent
+    pub parent_id: Option<Uuid>,
+    pub name: Option<String>,
+}
+
+impl Validate for UpdateLocationPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(name) = &self.name {
+            if errors.required("name", name) {
+                errors.max_len("name", name, PlaceName::MAX_LEN);
+            }
+        } else if self.parent_id.is_none() {
+            errors.add("body", "required", "at least one of name or parent_id must be given");
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Add a city, district, street or building (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/locations",
+    tag = LOCATION_TAG,
+    request_body = CreateLocationPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = LocationPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown `parent_id`", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 409, description = "`LOCATION_NAME_TAKEN` among its siblings", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`, including a kind that does not fit under the parent", body = ErrorResponse),
+    )
+)]
+async fn create_location(
+    State(app_state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<CreateLocationPayload>,
+) -> AppResult<Json<LocationPresenter>> {
+    let location = app_state.use_cases.create_location(payload.try_into()?).await?;
+
+    Ok(Json(location.into()))
+}
+
+/// The whole location tree, cities at the top
+#[utoipa::path(
+    get,
+    path = "/api/v1/locations",
+    tag = LOCATION_TAG,
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<LocationTreePresenter>),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn get_locations(
+    State(app_state): State<AppState>,
+) -> AppResult<Json<Vec<LocationTreePresenter>>> {
+    let locations = app_state.use_cases.get_locations().await?;
+
+    Ok(Json(LocationTreePresenter::build(locations)))
+}
+
+/// Rename or move a location (admin only)
+#[utoipa::path(
+    patch,
+    path = "/api/v1/locations/{id}",
+    tag = LOCATION_TAG,
+    params(("id" = Uuid, Path, description = "Location id")),
+    request_body = UpdateLocationPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = LocationPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown location or parent", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 409, description = "`LOCATION_NAME_TAKEN` among its siblings", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`, including a move below itself", body = ErrorResponse),
+    )
+)]
+async fn update_location(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<UpdateLocationPayload>,
+) -> AppResult<Json<LocationPresenter>> {
+    let location = app_state.use_cases.update_location(UpdateLocationDTO::try_new(id, payload)?).await?;
+
+    Ok(Json(location.into()))
+}
+
+/// Remove a location with no children and no chats (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/locations/{id}",
+    tag = LOCATION_TAG,
+    params(("id" = Uuid, Path, description = "Location id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = StatusMessage),
+        (status = 400, description = "`INVALID_PARAMS`: unknown location", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 409, description = "`LOCATION_IN_USE` by child locations or chats", body = ErrorResponse),
+    )
+)]
+async fn delete_location(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Json<Value>> {
+    app_state.use_cases.delete_location(id).await?;
+
+    Ok(Json(json!({
+        "message": "location deleted",
+        "status": "ok",
+    })))
+}
+
+/// District area as a GeoJSON `Polygon` or `MultiPolygon`, bare or wrapped in
+/// a `Feature` whose other members are ignored.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "type")]
+pub enum BoundaryPayload {
+    Polygon { coordinates: Vec<Vec<Vec<f64>>> },
+    MultiPolygon { coordinates: Vec<Vec<Vec<Vec<f64>>>> },
+    Feature { geometry: Geometry },
+}
+
+impl BoundaryPayload {
+    pub fn into_geometry(self) -> Geometry {
+        match self {
+            BoundaryPayload::Polygon { coordinates } => Geometry::Polygon { coordinates },
+            BoundaryPayload::MultiPolygon { coordinates } => Geometry::MultiPolygon { coordinates },
+            BoundaryPayload::Feature { geometry } => geometry,
+        }
+    }
+}
+
+impl Validate for BoundaryPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        if let BoundaryPayload::Feature { geometry: Geometry::Point { .. } } = self {
+            return Err(ValidationErrors::single("geometry", "invalid_type", "geometry must be a Polygon or MultiPolygon"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Upload the area of a district (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/v1/locations/{id}/boundary",
+    tag = LOCATION_TAG,
+    params(("id" = Uuid, Path, description = "District id")),
+    request_body = BoundaryPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "The district as a GeoJSON `Feature`", body = MapFeature),
+        (status = 400, description = "`INVALID_PARAMS`: unknown location", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`: malformed geometry, or not a district", body = ErrorResponse),
+    )
+)]
+async fn set_boundary(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<BoundaryPayload>,
+) -> AppResult<Json<MapFeature>> {
+    let district = app_state.use_cases.set_location_boundary(SetBoundaryDTO::try_new(id, payload)?).await?;
+
+    Ok(Json(district.into()))
+}
+
+/// Remove the area of a district (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/locations/{id}/boundary",
+    tag = LOCATION_TAG,
+    params(("id" = Uuid, Path, description = "District id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = StatusMessage),
+        (status = 400, description = "`INVALID_PARAMS`: unknown location", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn clear_boundary(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Json<Value>> {
+    app_state.use_cases.clear_location_boundary(id).await?;
+
+    Ok(Json(json!({
+        "message": "boundary removed",
+        "status": "ok",
+    })))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PointQuery {
+    /// Latitude in degrees
+    pub lat: f64,
+    /// Longitude in degrees
+    pub lon: f64,
+}
+
+impl Validate for PointQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.coordinates(Some(self.lat), Some(self.lon));
+
+        errors.into_result()
+    }
+}
+
+/// The district covering a point, and its chats
+#[utoipa::path(
+    get,
+    path = "/api/v1/locations/resolve",
+    tag = LOCATION_TAG,
+    params(PointQuery),
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = ResolvedDistrictPresenter),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn resolve_district(
+    State(app_state): State<AppState>,
+    viewer: ChatViewer,
+    ValidatedQuery(query): ValidatedQuery<PointQuery>,
+) -> AppResult<Json<ResolvedDistrictPresenter>> {
+    let point = GeoPoint::new(query.lat, query.lon)?;
+
+    let resolved = app_state.use_cases.resolve_district(point).await?;
+
+    Ok(Json(ResolvedDistrictPresenter::for_viewer(&viewer, resolved)))
+}
+
+/// District areas and chat positions as one GeoJSON `FeatureCollection`
+#[utoipa::path(
+    get,
+    path = "/api/v1/locations/map",
+    tag = LOCATION_TAG,
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = MapPresenter, content_type = "application/geo+json"),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn get_map(
+    State(app_state): State<AppState>,
+) -> AppResult<([(header::HeaderName, &'static str); 1], Json<MapPresenter>)> {
+    let map = app_state.use_cases.get_location_map().await?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/geo+json")],
+        Json(map.into()),
+    ))
+}
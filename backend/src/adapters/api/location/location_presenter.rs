@@ -0,0 +1,151 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    adapters::api::{chat::chat_presenter::{ChatPresenter, ChatViewer}, geojson::Geometry},
+    application::dto::location::{LocationMapDTO, ResolvedDistrictDTO},
+    domain::entities::{chat::Chat, location::{District, LocationNode}},
+};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LocationPresenter {
+    pub id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub kind: String,
+    pub name: String,
+}
+
+impl From<LocationNode> for LocationPresenter {
+    fn from(value: LocationNode) -> Self {
+        Self {
+            id: value.id,
+            parent_id: value.parent_id,
+            kind: value.kind.as_str().to_string(),
+            name: value.name.to_string(),
+        }
+    }
+}
+
+/// A location with everything below it.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LocationTreePresenter {
+    pub id: Uuid,
+    pub kind: String,
+    pub name: String,
+    #[schema(no_recursion)]
+    pub children: Vec<LocationTreePresenter>,
+}
+
+impl LocationTreePresenter {
+    /// Nests the flat list under its cities, siblings sorted by name.
+    pub fn build(mut nodes: Vec<LocationNode>) -> Vec<Self> {
+        nodes.sort_by(|a, b| a.name.as_str().cmp(b.name.as_str()));
+
+        Self::children_of(None, &nodes)
+    }
+
+    fn children_of(parent_id: Option<Uuid>, nodes: &[LocationNode]) -> Vec<Self> {
+        nodes
+            .iter()
+            .filter(|n| n.parent_id == parent_id)
+            .map(|n| Self {
+                id: n.id,
+                kind: n.kind.as_str().to_string(),
+                name: n.name.to_string(),
+                children: Self::children_of(Some(n.id), nodes),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ResolvedDistrictPresenter {
+    /// `null` when no district covers the point
+    pub district: Option<LocationPresenter>,
+    /// Chats in the district and the locations below it
+    pub chats: Vec<ChatPresenter>,
+}
+
+impl ResolvedDistrictPresenter {
+    pub fn for_viewer(viewer: &ChatViewer, value: Option<ResolvedDistrictDTO>) -> Self {
+        match value {
+            Some(resolved) => Self {
+                district: Some(resolved.district.into()),
+                chats: resolved.chats.into_iter().map(|c| ChatPresenter::for_viewer(viewer, c)).collect(),
+            },
+            None => Self { district: None, chats: Vec::new() },
+        }
+    }
+}
+
+/// GeoJSON `FeatureCollection` of the whole map.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MapPresenter {
+    /// Always `FeatureCollection`
+    #[serde(rename = "type")]
+    #[schema(value_type = String)]
+    pub kind: &'static str,
+    pub features: Vec<MapFeature>,
+}
+
+impl From<LocationMapDTO> for MapPresenter {
+    fn from(value: LocationMapDTO) -> Self {
+        let districts = value.districts.into_iter().map(MapFeature::from);
+        let chats = value.chats.into_iter().filter_map(MapFeature::from_chat);
+
+        Self {
+            kind: "FeatureCollection",
+            features: districts.chain(chats).collect(),
+        }
+    }
+}
+
+/// GeoJSON `Feature`: a district area or a chat position.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MapFeature {
+    /// Always `Feature`
+    #[serde(rename = "type")]
+    #[schema(value_type = String)]
+    pub kind: &'static str,
+    pub id: Uuid,
+    pub geometry: Geometry,
+    pub properties: MapFeatureProperties,
+}
+
+impl MapFeature {
+    /// Chats without coordinates have no place on the map.
+    fn from_chat(chat: Chat) -> Option<Self> {
+        Some(Self {
+            kind: "Feature",
+            id: chat.id,
+            geometry: chat.position?.into(),
+            properties: MapFeatureProperties::Chat {
+                name: chat.name.to_string(),
+                users_count: chat.users_count.get(),
+                location_id: chat.location_id,
+            },
+        })
+    }
+}
+
+impl From<District> for MapFeature {
+    fn from(value: District) -> Self {
+        Self {
+            kind: "Feature",
+            id: value.location.id,
+            geometry: (&value.boundary).into(),
+            properties: MapFeatureProperties::District {
+                name: value.location.name.to_string(),
+                parent_id: value.location.parent_id,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(tag = "layer", rename_all = "lowercase")]
+pub enum MapFeatureProperties {
+    District { name: String, parent_id: Option<Uuid> },
+    Chat { name: String, users_count: u64, location_id: Option<Uuid> },
+}
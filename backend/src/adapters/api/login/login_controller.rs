@@ -1,24 +1,50 @@
-use axum::{Json, extract::State};
+use axum::{Json, extract::State, middleware, routing::post};
 use serde_json::{Value, json};
-use tower_cookies::{Cookie, Cookies};
+use tower_cookies::Cookies;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
 
-use crate::{adapters::api::{AUTH_TOKEN, app_state::AppState, user::user_payload::LoginUserPayload}, application::{AppError, AppResult}};
+use crate::{adapters::api::{AUTH_TOKEN, CSRF_TOKEN, app_state::AppState, middlewares, extractors::ValidatedJson, openapi::{AUTH_TAG, ErrorResponse, SuccessResult}, user::user_payload::LoginUserPayload}, application::{AppError, AppResult}};
 
+pub fn login_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(login))
+        .routes(routes!(logout))
+}
+
+pub fn legacy_login_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .route("/api/login", post(login))
+        .route("/api/logout", post(logout))
+        .layer(middleware::map_response(middlewares::mark_deprecated))
+}
+
+/// Start a session: sets the `auth-token` and `csrf-token` cookies
+#[utoipa::path(
+    post,
+    path = "/api/v1/login",
+    tag = AUTH_TAG,
+    request_body = LoginUserPayload,
+    responses(
+        (status = 200, body = SuccessResult),
+        (status = 403, description = "`LOGIN_FAIL`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
 pub async fn login(
     State(app_state): State<AppState>,
     cookies: Cookies,
-    Json(payload): Json<LoginUserPayload>,
+    ValidatedJson(payload): ValidatedJson<LoginUserPayload>,
 ) -> AppResult<Json<Value>> {
     let login_response = app_state.use_cases
         .login_user(payload.into())
         .await
         .map_err(|_|AppError::LoginFail)?;
 
-    let mut auth_cookie = Cookie::new(AUTH_TOKEN, login_response.token);
-    auth_cookie.set_http_only(true);
-    auth_cookie.set_path("/");
+    let csrf_token = Uuid::new_v4().simple().to_string();
 
-    cookies.add(auth_cookie);
+    cookies.add(app_state.cookies.auth_cookie(login_response.token));
+    cookies.add(app_state.cookies.csrf_cookie(csrf_token));
 
     Ok(
         Json(
@@ -33,14 +59,19 @@ pub async fn login(
     )
 }
 
+/// End the session by expiring both cookies
+#[utoipa::path(
+    post,
+    path = "/api/v1/logout",
+    tag = AUTH_TAG,
+    responses((status = 200, body = SuccessResult))
+)]
 pub async fn logout(
+    State(app_state): State<AppState>,
     cookies: Cookies,
 ) -> AppResult<Json<Value>> {
-    let mut auth_cookie = Cookie::from(AUTH_TOKEN);
-
-    auth_cookie.set_path("/");
-
-    cookies.remove(auth_cookie);
+    cookies.remove(app_state.cookies.removal_cookie(AUTH_TOKEN));
+    cookies.remove(app_state.cookies.removal_cookie(CSRF_TOKEN));
 
     Ok(
         Json(
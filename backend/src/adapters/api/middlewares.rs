@@ -1,17 +1,18 @@
 use std::sync::Arc;
 
 use axum::{
-    Json, body::Body, extract::{Request, State}, http::{Method, Uri}, middleware::Next, response::{IntoResponse, Response}
+    Json, body::Body, extract::{Request, State}, http::{HeaderMap, HeaderValue, Method, Uri, header}, middleware::Next, response::{IntoResponse, Response}
 };
 use serde_json::json;
-use tower_cookies::{Cookie, Cookies};
+use tower_cookies::Cookies;
 use uuid::Uuid;
 
 use crate::{
     adapters::{
-        api::{AUTH_TOKEN, app_state::AppState}, crypto::token::Token, ctx::{Ctx, CtxError, CtxResult}
+        api::{AUTH_TOKEN, CSRF_HEADER, CSRF_TOKEN, app_state::AppState, i18n::Lang}, crypto::token::Token, ctx::{Ctx, CtxError, CtxResult}
     },
     application::{AppError, AppResult, dto::user::GetUserByIdDTO},
+    domain::entities::user::UserRole,
 };
 
 pub async fn require_auth(
@@ -29,6 +30,33 @@ pub async fn require_auth(
     Ok(next.run(req).await)
 }
 
+/// Route-level admin guard, for routes that are not under the `/api/admin` prefix.
+pub async fn require_admin(
+    ctx: AppResult<Ctx>,
+    req: Request<Body>,
+    next: Next,
+) -> AppResult<Response> {
+    println!("->> {:<12} - middleware_require_admin - {ctx:?}", "MIDDLEWARE");
+
+    let ctx = ctx?;
+
+    if ctx.get_user_role() != UserRole::Admin.as_str() {
+        return Err(AppError::Context(CtxError::UserNotAdmin));
+    }
+
+    Ok(next.run(req).await)
+}
+
+/// Marks pre-`/api/v1` routes so clients notice before they are removed.
+pub async fn mark_deprecated(mut res: Response) -> Response {
+    let headers = res.headers_mut();
+
+    headers.insert("deprecation", HeaderValue::from_static("true"));
+    headers.insert(header::LINK, HeaderValue::from_static("</api/v1>; rel=\"successor-version\""));
+
+    res
+}
+
 pub async fn context_resolver(
     State(app_state): State<AppState>,
     uri: Uri,
@@ -36,10 +64,10 @@ pub async fn context_resolver(
     mut req: Request<Body>,
     next: Next,
 ) -> Response {
-    let result = resolve_context(app_state, &cookies, uri).await;
+    let result = resolve_context(app_state.clone(), &cookies, uri).await;
 
     if result.is_err() && !matches!(result, Err(CtxError::NoTokenInCookies)) {
-        cookies.remove(Cookie::from(AUTH_TOKEN));
+        cookies.remove(app_state.cookies.removal_cookie(AUTH_TOKEN));
     }
 
     req.extensions_mut().insert(result);
@@ -80,10 +108,80 @@ async fn resolve_context(app_state: AppState, cookies: &Cookies, uri: Uri) -> Ct
 }
 
 
+/// Rejects cross-site state-changing requests: the `Origin` (or `Referer`)
+/// must be the API host or a trusted origin, and cookie-authenticated requests
+/// must echo the CSRF cookie in the `X-CSRF-Token` header (double submit).
+pub async fn csrf_protection(
+    State(app_state): State<AppState>,
+    cookies: Cookies,
+    req: Request<Body>,
+    next: Next,
+) -> AppResult<Response> {
+    if matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        return Ok(next.run(req).await);
+    }
+
+    if !origin_allowed(req.headers(), &app_state.trusted_origins) {
+        println!("->> {:<12} - csrf_protection - origin rejected", "MIDDLEWARE");
+
+        return Err(AppError::CsrfCheckFailed);
+    }
+
+    if cookies.get(AUTH_TOKEN).is_some() {
+        let cookie_token = cookies.get(CSRF_TOKEN).map(|c| c.value().to_string());
+        let header_token = req
+            .headers()
+            .get(CSRF_HEADER)
+            .and_then(|h| h.to_str().ok());
+
+        match (cookie_token, header_token) {
+            (Some(cookie_token), Some(header_token))
+                if !cookie_token.is_empty() && cookie_token == header_token => {}
+            _ => {
+                println!("->> {:<12} - csrf_protection - token mismatch", "MIDDLEWARE");
+
+                return Err(AppError::CsrfCheckFailed);
+            }
+        }
+    }
+
+    Ok(next.run(req).await)
+}
+
+fn origin_allowed(headers: &HeaderMap, trusted_origins: &[String]) -> bool {
+    let header_str = |name| headers.get(name).and_then(|h| h.to_str().ok());
+
+    // Browsers omit Origin on some same-origin requests; Referer carries the same information.
+    let origin = match header_str(header::ORIGIN) {
+        Some(origin) => origin.to_string(),
+        None => match header_str(header::REFERER).and_then(origin_of_url) {
+            Some(origin) => origin,
+            // Not a browser request, nothing a cookie could be forged for.
+            None => return true,
+        },
+    };
+
+    if trusted_origins.iter().any(|o| o.trim_end_matches('/') == origin) {
+        return true;
+    }
+
+    let origin_host = origin.split_once("://").map(|(_, host)| host);
+
+    origin_host.is_some() && origin_host == header_str(header::HOST)
+}
+
+fn origin_of_url(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+
+    Some(format!("{scheme}://{authority}"))
+}
+
 pub async fn main_response_middleware(
     ctx: AppResult<Ctx>,
     uri: Uri,
     req_method: Method,
+    req_headers: HeaderMap,
     res: Response,
 ) -> Response {
     println!("->> {:<12} - main_response_mapper", "RES_MAPPER");
@@ -97,19 +195,80 @@ pub async fn main_response_middleware(
     let error_response = client_error
         .map(
             |(status_code, client_error)| {
-                let error_response_body = json!({
+                let lang = Lang::negotiate(&req_headers);
+
+                let mut error_response_body = json!({
                     "error": {
                         "type": client_error,
+                        "status": status_code.as_u16(),
+                        "message": client_error.message(lang),
                         "req_uuid": request_uuid.to_string(),
                     }
                 });
 
+                if let Some(fields) = service_error.and_then(|e| e.field_errors()) {
+                    error_response_body["error"]["fields"] = json!(fields);
+                }
+
                 println!(" -->> client_error_body: {error_response_body}");
 
-                (status_code, Json(error_response_body)).into_response()
+                let mut error_response = (
+                    status_code,
+                    [(header::CONTENT_LANGUAGE, lang.as_str())],
+                    Json(error_response_body),
+                )
+                    .into_response();
+
+                // Keep headers set by inner layers, e.g. the deprecation notice
+                for (name, value) in res.headers() {
+                    if name != header::CONTENT_TYPE && name != header::CONTENT_LENGTH {
+                        error_response.headers_mut().insert(name.clone(), value.clone());
+                    }
+                }
+
+                error_response
             });
 
     println!(" ->> app log - uri: {uri} - {request_uuid} - Error: {service_error:?} - method: {req_method:?} - user: {ctx:?}");
 
     error_response.unwrap_or(res)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(header::HeaderName, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+
+        for (name, value) in pairs {
+            headers.insert(name, value.parse().unwrap());
+        }
+
+        headers
+    }
+
+    #[test]
+    fn test_origin_allowed_same_host_and_trusted() {
+        let trusted = vec!["http://localhost:3000".to_string()];
+
+        let same_host = headers(&[(header::ORIGIN, "https://api.example.org"), (header::HOST, "api.example.org")]);
+        let trusted_origin = headers(&[(header::ORIGIN, "http://localhost:3000"), (header::HOST, "localhost:8000")]);
+        let via_referer = headers(&[(header::REFERER, "http://localhost:3000/admin?tab=1"), (header::HOST, "localhost:8000")]);
+        let no_browser = headers(&[(header::HOST, "localhost:8000")]);
+
+        assert!(origin_allowed(&same_host, &trusted));
+        assert!(origin_allowed(&trusted_origin, &trusted));
+        assert!(origin_allowed(&via_referer, &trusted));
+        assert!(origin_allowed(&no_browser, &trusted));
+    }
+
+    #[test]
+    fn test_origin_rejected_for_foreign_site() {
+        let foreign = headers(&[(header::ORIGIN, "https://evil.example"), (header::HOST, "api.example.org")]);
+        let opaque = headers(&[(header::ORIGIN, "null"), (header::HOST, "api.example.org")]);
+
+        assert!(!origin_allowed(&foreign, &[]));
+        assert!(!origin_allowed(&opaque, &[]));
+    }
+}
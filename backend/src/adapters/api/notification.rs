@@ -0,0 +1,2 @@
+pub mod notification_controller;
+pub mod notification_presenter;
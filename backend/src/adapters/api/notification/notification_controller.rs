@@ -0,0 +1,71 @@
+use axum::{Json, extract::State, middleware};
+use serde_json::{Value, json};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+use crate::{
+    adapters::{
+        api::{
+            app_state::AppState,
+            extractors::ValidatedPath,
+            middlewares,
+            notification::notification_presenter::NotificationPresenter,
+            openapi::{ErrorResponse, NOTIFICATION_TAG, StatusMessage},
+        },
+        ctx::Ctx,
+    },
+    application::AppResult,
+};
+
+pub fn notification_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(get_notifications))
+        .routes(routes!(mark_notification_read))
+        .route_layer(middleware::from_fn(middlewares::require_auth))
+}
+
+/// The caller's notifications, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications",
+    tag = NOTIFICATION_TAG,
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<NotificationPresenter>),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn get_notifications(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+) -> AppResult<Json<Vec<NotificationPresenter>>> {
+    let notifications = app_state.use_cases.get_notifications(ctx.get_user_id()).await?;
+
+    Ok(Json(notifications.into_iter().map(|n| n.into()).collect()))
+}
+
+/// Mark one of the caller's notifications as read
+#[utoipa::path(
+    post,
+    path = "/api/v1/notifications/{id}/read",
+    tag = NOTIFICATION_TAG,
+    params(("id" = Uuid, Path, description = "Notification id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = StatusMessage),
+        (status = 400, description = "`INVALID_PARAMS`: unknown notification", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn mark_notification_read(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Json<Value>> {
+    app_state.use_cases.mark_notification_read(ctx.get_user_id(), id).await?;
+
+    Ok(Json(json!({
+        "message": "notification read",
+        "status": "ok",
+    })))
+}
@@ -0,0 +1,29 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::entities::notification::Notification;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationPresenter {
+    pub id: Uuid,
+    /// `submission_approved`, `submission_rejected` or `submission_changes_requested`
+    pub kind: String,
+    pub submission_id: Option<Uuid>,
+    pub note: Option<String>,
+    pub created_at: String,
+    pub read: bool,
+}
+
+impl From<Notification> for NotificationPresenter {
+    fn from(value: Notification) -> Self {
+        Self {
+            id: value.id,
+            kind: value.kind.as_str().to_string(),
+            submission_id: value.submission_id,
+            note: value.note,
+            created_at: value.created_at.to_rfc3339(),
+            read: value.read_at.is_some(),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use axum::{Json, Router, routing::get};
+use serde::Serialize;
+use utoipa::{
+    Modify, OpenApi, ToSchema,
+    openapi::security::{ApiKey, ApiKeyValue, SecurityScheme},
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+use uuid::Uuid;
+
+use crate::{
+    adapters::api::{
+        AUTH_TOKEN, CSRF_HEADER,
+        app_state::AppState,
+        chat::chat_controller::{chat_membership_router, chat_router, legacy_chat_router},
+        location::location_controller::location_router,
+        tag::tag_controller::tag_router,
+        submission::submission_controller::{admin_submission_router, submission_router},
+        notification::notification_controller::notification_router,
+        favorite::favorite_controller::{favorite_router, user_favorite_router},
+        errors::{__path_list_error_codes, ClientError, list_error_codes},
+        login::login_controller::{legacy_login_router, login_router},
+        user::user_controller::{legacy_user_router, user_account_router, user_router},
+    },
+    application::validation::FieldError,
+};
+
+pub const OPENAPI_PATH: &str = "/api/openapi.json";
+pub const DOCS_UI_PATH: &str = "/api/docs";
+
+pub const COOKIE_AUTH: &str = "cookie_auth";
+pub const CSRF_AUTH: &str = "csrf_token";
+
+pub const USER_TAG: &str = "users";
+pub const CHAT_TAG: &str = "chats";
+pub const LOCATION_TAG: &str = "locations";
+pub const SUBMISSION_TAG: &str = "chat-submissions";
+pub const NOTIFICATION_TAG: &str = "notifications";
+pub const AUTH_TAG: &str = "auth";
+pub const META_TAG: &str = "meta";
+
+/// Body of every non-2xx response produced by `main_response_middleware`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: ErrorDetail,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorDetail {
+    #[serde(rename = "type")]
+    pub kind: ClientError,
+    pub status: u16,
+    /// Localised from `Accept-Language`
+    pub message: String,
+    pub req_uuid: Uuid,
+    /// Present on `VALIDATION_ERROR` only
+    pub fields: Option<Vec<FieldError>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StatusMessage {
+    pub message: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SuccessResult {
+    pub result: Success,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Success {
+    pub success: bool,
+}
+
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+
+        components.add_security_scheme(
+            COOKIE_AUTH,
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::with_description(
+                AUTH_TOKEN,
+                "Set by `POST /api/v1/login`",
+            ))),
+        );
+
+        components.add_security_scheme(
+            CSRF_AUTH,
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                CSRF_HEADER,
+                "Value of the `csrf-token` cookie, required on non-GET requests with a session",
+            ))),
+        );
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "NCity chat network API"),
+    components(schemas(ErrorResponse, ErrorDetail, ClientError, FieldError, StatusMessage, SuccessResult)),
+    modifiers(&SecuritySchemes),
+    tags(
+        (name = USER_TAG, description = "Accounts and profiles"),
+        (name = CHAT_TAG, description = "Chat directory"),
+        (name = LOCATION_TAG, description = "City, district, street and building tree"),
+        (name = SUBMISSION_TAG, description = "Chats proposed by residents and their review"),
+        (name = NOTIFICATION_TAG, description = "In-app notifications"),
+        (name = AUTH_TAG, description = "Session cookie login"),
+        (name = META_TAG, description = "API self-description"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Every API route, split into the axum router and the spec. Only `/api/v1`
+/// is documented; the deprecated unversioned routes and the `/api/admin`
+/// submission queue are served alongside.
+pub fn api_routes() -> OpenApiRouter<AppState> {
+    OpenApiRouter::with_openapi(ApiDoc::openapi())
+        .merge(user_router())
+        .merge(chat_router())
+        .merge(location_router())
+        .merge(tag_router())
+        .merge(submission_router())
+        .merge(notification_router())
+        .merge(favorite_router())
+        .merge(login_router())
+        .routes(routes!(list_error_codes))
+        .merge(legacy_user_router())
+        .merge(user_account_router())
+        .merge(legacy_chat_router())
+        .merge(chat_membership_router())
+        .merge(legacy_login_router())
+        .merge(user_favorite_router())
+        .merge(admin_submission_router())
+}
+
+/// Serves the generated document and, if enabled, an interactive viewer.
+pub fn docs_router(openapi: utoipa::openapi::OpenApi, with_ui: bool) -> Router {
+    let document = Arc::new(openapi);
+
+    let mut router = Router::new().route(
+        OPENAPI_PATH,
+        get({
+            let document = document.clone();
+            move || async move { Json(document.as_ref().clone()) }
+        }),
+    );
+
+    if with_ui {
+        router = router.merge(Scalar::with_url(DOCS_UI_PATH, document.as_ref().clone()));
+    }
+
+    router
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spec_covers_routes_and_auth() {
+        let (_, openapi) = api_routes().split_for_parts();
+
+        for path in ["/api/v1/users", "/api/v1/users/me", "/api/v1/users/public", "/api/v1/users/me/privacy", "/api/v1/users/me/export", "/api/v1/users/me/favorites", "/api/v1/users/me/favorites/{chat_id}", "/api/v1/users/{id}", "/api/v1/chats", "/api/v1/chats/{id}", "/api/v1/chats/deleted", "/api/v1/chats/nearby", "/api/v1/chats/facets", "/api/v1/tags", "/api/v1/tags/{id}", "/api/v1/chats/{id}/restore", "/api/v1/chats/{id}/owner", "/api/v1/chats/{id}/join", "/api/v1/chats/{id}/leave", "/api/v1/chats/{id}/members", "/api/v1/chats/most-followed", "/api/v1/chats/dead-links", "/api/v1/chats/{id}/link-checks", "/c/{id}", "/api/v1/chat-submissions", "/api/v1/chat-submissions/mine", "/api/v1/chat-submissions/{id}", "/api/v1/chat-submissions/{id}/approve", "/api/v1/chat-submissions/{id}/reject", "/api/v1/chat-submissions/{id}/request-changes", "/api/v1/notifications", "/api/v1/notifications/{id}/read", "/api/v1/locations", "/api/v1/locations/{id}", "/api/v1/locations/{id}/boundary", "/api/v1/locations/resolve", "/api/v1/locations/map", "/api/v1/users/deleted", "/api/v1/users/{id}/restore", "/api/v1/login", "/api/v1/logout", "/api/errors"] {
+            assert!(openapi.paths.paths.contains_key(path), "{path} missing from spec");
+        }
+
+        assert!(!openapi.paths.paths.contains_key("/api/user-by-id"), "legacy routes stay undocumented");
+
+        let components = openapi.components.expect("components");
+
+        for schema in ["NewUserPayload", "CreateChatPayload", "UserPresenter", "PublicUserPresenter", "ChatPresenter", "ErrorResponse", "ClientError"] {
+            assert!(components.schemas.contains_key(schema), "{schema} missing from spec");
+        }
+
+        assert!(components.security_schemes.contains_key(COOKIE_AUTH));
+    }
+}
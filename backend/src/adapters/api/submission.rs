@@ -0,0 +1,2 @@
+pub mod submission_controller;
+pub mod submission_presenter;
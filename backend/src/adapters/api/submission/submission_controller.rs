@@ -0,0 +1,294 @@
+use axum::{Json, extract::State, middleware, routing::{get, post}};
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+use crate::{
+    adapters::{
+        api::{
+            app_state::AppState,
+            chat::chat_controller::DESCRIPTION_MAX_LEN,
+            extractors::{ValidatedJson, ValidatedPath, ValidatedQuery},
+            middlewares,
+            openapi::{ErrorResponse, SUBMISSION_TAG},
+            submission::submission_presenter::SubmissionPresenter,
+        },
+        ctx::Ctx,
+    },
+    application::{AppResult, dto::submission::{ReviewDTO, ReviseSubmissionDTO}, validation::{Validate, ValidationErrors}},
+    domain::entities::{chat::ChatCategory, submission::SubmissionStatus, value_objects::{ChatName, Location}},
+};
+
+pub const REVIEW_REASON_MAX_LEN: usize = 1000;
+
+pub fn submission_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(get_submission_queue))
+        .routes(routes!(approve_submission))
+        .routes(routes!(reject_submission))
+        .routes(routes!(request_submission_changes))
+        .route_layer(middleware::from_fn(middlewares::require_admin))
+        .routes(routes!(submit_chat))
+        .routes(routes!(get_own_submissions))
+        .routes(routes!(revise_submission))
+        .route_layer(middleware::from_fn(middlewares::require_auth))
+}
+
+/// The admin queue under the `/api/admin` prefix, which `context_resolver`
+/// already restricts to admins.
+pub fn admin_submission_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .route("/api/admin/chat-submissions", get(get_submission_queue))
+        .route("/api/admin/chat-submissions/{id}/approve", post(approve_submission))
+        .route("/api/admin/chat-submissions/{id}/reject", post(reject_submission))
+        .route("/api/admin/chat-submissions/{id}/request-changes", post(request_submission_changes))
+        .route_layer(middleware::from_fn(middlewares::require_auth))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChatProposalPayload {
+    pub name: String,
+    pub location: String,
+    #[serde(default)]
+    pub description: String,
+    /// WGS84 degrees, given together with `longitude`
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Node of the location tree the chat belongs to
+    pub location_id: Option<Uuid>,
+    /// One of `ChatCategory`, `general` if omitted
+    pub category: Option<String>,
+}
+
+impl Validate for ChatProposalPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if errors.required("name", &self.name) {
+            errors.max_len("name", &self.name, ChatName::MAX_LEN);
+        }
+
+        if errors.required("location", &self.location) {
+            errors.max_len("location", &self.location, Location::MAX_LEN);
+        }
+
+        errors.max_len("description", &self.description, DESCRIPTION_MAX_LEN);
+        errors.coordinates(self.latitude, self.longitude);
+
+        if let Some(category) = &self.category {
+            errors.parse::<ChatCategory>("category", category);
+        }
+
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReviewPayload {
+    /// Shown to the submitter
+    pub reason: String,
+}
+
+impl Validate for ReviewPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if errors.required("reason", &self.reason) {
+            errors.max_len("reason", &self.reason, REVIEW_REASON_MAX_LEN);
+        }
+
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SubmissionsQuery {
+    /// `pending` (default), `changes_requested`, `approved` or `rejected`
+    pub status: Option<String>,
+}
+
+impl Validate for SubmissionsQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(status) = &self.status {
+            errors.parse::<SubmissionStatus>("status", status);
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Propose a chat for the directory; it is listed once an admin approves it
+#[utoipa::path(
+    post,
+    path = "/api/v1/chat-submissions",
+    tag = SUBMISSION_TAG,
+    request_body = ChatProposalPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = SubmissionPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown `location_id`", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`, also for suspended accounts", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn submit_chat(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    ValidatedJson(payload): ValidatedJson<ChatProposalPayload>,
+) -> AppResult<Json<SubmissionPresenter>> {
+    let submission = app_state.use_cases.submit_chat(ctx.get_user_id(), payload.try_into()?).await?;
+
+    Ok(Json(submission.into()))
+}
+
+/// The caller's own submissions, oldest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/chat-submissions/mine",
+    tag = SUBMISSION_TAG,
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<SubmissionPresenter>),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn get_own_submissions(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+) -> AppResult<Json<Vec<SubmissionPresenter>>> {
+    let submissions = app_state.use_cases.get_own_submissions(ctx.get_user_id()).await?;
+
+    Ok(Json(submissions.into_iter().map(|s| s.into()).collect()))
+}
+
+/// Replace the proposal of a pending submission, or one sent back for
+/// changes, which puts it back in the queue
+#[utoipa::path(
+    put,
+    path = "/api/v1/chat-submissions/{id}",
+    tag = SUBMISSION_TAG,
+    params(("id" = Uuid, Path, description = "Submission id")),
+    request_body = ChatProposalPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = SubmissionPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown submission or `location_id`", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`, also for someone else's submission", body = ErrorResponse),
+        (status = 409, description = "`SUBMISSION_DECIDED`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn revise_submission(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<ChatProposalPayload>,
+) -> AppResult<Json<SubmissionPresenter>> {
+    let submission = app_state
+        .use_cases
+        .revise_submission(ctx.get_user_id(), ReviseSubmissionDTO::try_new(id, payload)?)
+        .await?;
+
+    Ok(Json(submission.into()))
+}
+
+/// Submissions awaiting review, oldest first (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/chat-submissions",
+    tag = SUBMISSION_TAG,
+    params(SubmissionsQuery),
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<SubmissionPresenter>),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn get_submission_queue(
+    State(app_state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<SubmissionsQuery>,
+) -> AppResult<Json<Vec<SubmissionPresenter>>> {
+    let submissions = app_state.use_cases.get_submission_queue(query.try_into()?).await?;
+
+    Ok(Json(submissions.into_iter().map(|s| s.into()).collect()))
+}
+
+/// List the proposed chat and notify the submitter (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/chat-submissions/{id}/approve",
+    tag = SUBMISSION_TAG,
+    params(("id" = Uuid, Path, description = "Submission id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, description = "The submission, with `chat_id` set", body = SubmissionPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown submission, or its location is gone", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 409, description = "`SUBMISSION_DECIDED` or `CHAT_NAME_TAKEN`", body = ErrorResponse),
+    )
+)]
+async fn approve_submission(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Json<SubmissionPresenter>> {
+    let submission = app_state.use_cases.approve_submission(id).await?;
+
+    Ok(Json(submission.into()))
+}
+
+/// Turn a submission down with a reason for the submitter (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/chat-submissions/{id}/reject",
+    tag = SUBMISSION_TAG,
+    params(("id" = Uuid, Path, description = "Submission id")),
+    request_body = ReviewPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = SubmissionPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown submission", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 409, description = "`SUBMISSION_DECIDED`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn reject_submission(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<ReviewPayload>,
+) -> AppResult<Json<SubmissionPresenter>> {
+    let submission = app_state.use_cases.reject_submission(ReviewDTO::new(id, payload)).await?;
+
+    Ok(Json(submission.into()))
+}
+
+/// Send a submission back to its submitter with what to change (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/chat-submissions/{id}/request-changes",
+    tag = SUBMISSION_TAG,
+    params(("id" = Uuid, Path, description = "Submission id")),
+    request_body = ReviewPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = SubmissionPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown submission", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 409, description = "`SUBMISSION_DECIDED`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn request_submission_changes(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<ReviewPayload>,
+) -> AppResult<Json<SubmissionPresenter>> {
+    let submission = app_state.use_cases.request_submission_changes(ReviewDTO::new(id, payload)).await?;
+
+    Ok(Json(submission.into()))
+}
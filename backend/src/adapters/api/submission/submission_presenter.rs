@@ -0,0 +1,45 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::entities::submission::ChatSubmission;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SubmissionPresenter {
+    pub id: Uuid,
+    pub submitter_id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub location: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub location_id: Option<Uuid>,
+    pub category: String,
+    /// `pending`, `changes_requested`, `approved` or `rejected`
+    pub status: String,
+    /// Reason for a rejection or change request
+    pub review_note: Option<String>,
+    /// The listed chat, once approved
+    pub chat_id: Option<Uuid>,
+}
+
+impl From<ChatSubmission> for SubmissionPresenter {
+    fn from(value: ChatSubmission) -> Self {
+        let proposal = value.proposal;
+
+        Self {
+            id: value.id,
+            submitter_id: value.submitter_id,
+            name: proposal.name.to_string(),
+            description: proposal.description,
+            location: proposal.location.to_string(),
+            latitude: proposal.position.map(|p| p.lat()),
+            longitude: proposal.position.map(|p| p.lon()),
+            location_id: proposal.location_id,
+            category: proposal.category.as_str().to_string(),
+            status: value.status.as_str().to_string(),
+            review_note: value.review_note,
+            chat_id: value.chat_id,
+        }
+    }
+}
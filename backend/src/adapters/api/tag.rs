@@ -0,0 +1,2 @@
+pub mod tag_controller;
+pub mod tag_presenter;
@@ -0,0 +1,142 @@
+use axum::{Json, extract::State, middleware};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use utoipa::ToSchema;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
+
+use crate::{
+    adapters::api::{
+        app_state::AppState,
+        extractors::{ValidatedJson, ValidatedPath},
+        middlewares,
+        openapi::{CHAT_TAG, ErrorResponse, StatusMessage},
+        tag::tag_presenter::TagPresenter,
+    },
+    application::{AppResult, dto::tag::RenameTagDTO, validation::{Validate, ValidationErrors}},
+    domain::entities::value_objects::TagName,
+};
+
+pub fn tag_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(create_tag))
+        .routes(routes!(rename_tag, delete_tag))
+        .route_layer(middleware::from_fn(middlewares::require_admin))
+        .routes(routes!(get_tags))
+        .route_layer(middleware::from_fn(middlewares::require_auth))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TagPayload {
+    /// Stored lower-cased
+    pub name: String,
+}
+
+impl Validate for TagPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if errors.required("name", &self.name) {
+            errors.max_len("name", &self.name, TagName::MAX_LEN);
+
+            if self.name.contains(',') {
+                errors.add("name", "invalid_format", "name must not contain commas");
+            }
+        }
+
+        errors.into_result()
+    }
+}
+
+/// The tag vocabulary, alphabetically
+#[utoipa::path(
+    get,
+    path = "/api/v1/tags",
+    tag = CHAT_TAG,
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<TagPresenter>),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn get_tags(
+    State(app_state): State<AppState>,
+) -> AppResult<Json<Vec<TagPresenter>>> {
+    let tags = app_state.use_cases.get_tags().await?;
+
+    Ok(Json(tags.into_iter().map(|t| t.into()).collect()))
+}
+
+/// Add a tag to the vocabulary (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/tags",
+    tag = CHAT_TAG,
+    request_body = TagPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = TagPresenter),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 409, description = "`TAG_NAME_TAKEN`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn create_tag(
+    State(app_state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<TagPayload>,
+) -> AppResult<Json<TagPresenter>> {
+    let tag = app_state.use_cases.create_tag(payload.try_into()?).await?;
+
+    Ok(Json(tag.into()))
+}
+
+/// Rename a tag everywhere it is used (admin only)
+#[utoipa::path(
+    patch,
+    path = "/api/v1/tags/{id}",
+    tag = CHAT_TAG,
+    params(("id" = Uuid, Path, description = "Tag id")),
+    request_body = TagPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = TagPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: unknown tag", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 409, description = "`TAG_NAME_TAKEN`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn rename_tag(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+    ValidatedJson(payload): ValidatedJson<TagPayload>,
+) -> AppResult<Json<TagPresenter>> {
+    let tag = app_state.use_cases.rename_tag(RenameTagDTO::try_new(id, payload)?).await?;
+
+    Ok(Json(tag.into()))
+}
+
+/// Remove a tag from the vocabulary and from every chat (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/v1/tags/{id}",
+    tag = CHAT_TAG,
+    params(("id" = Uuid, Path, description = "Tag id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = StatusMessage),
+        (status = 400, description = "`INVALID_PARAMS`: unknown tag", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn delete_tag(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Json<Value>> {
+    app_state.use_cases.delete_tag(id).await?;
+
+    Ok(Json(json!({
+        "message": "tag deleted",
+        "status": "ok",
+    })))
+}
@@ -0,0 +1,20 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::entities::tag::Tag;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TagPresenter {
+    pub id: Uuid,
+    pub name: String,
+}
+
+impl From<Tag> for TagPresenter {
+    fn from(value: Tag) -> Self {
+        Self {
+            id: value.id,
+            name: value.name.to_string(),
+        }
+    }
+}
@@ -1,49 +1,108 @@
 use axum::{
-    Json, Router,
+    Json,
     extract::State,
+    http::{HeaderMap, header},
     middleware,
     routing::{delete, get, post},
 };
+use secrecy::ExposeSecret;
 use serde_json::{Value, json};
+use tower_cookies::Cookies;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use uuid::Uuid;
 
 use crate::{
     adapters::{
         api::{
+            AUTH_TOKEN, CSRF_TOKEN,
             app_state::AppState,
+            extractors::{ValidatedJson, ValidatedPath, ValidatedQuery},
             middlewares,
+            openapi::{ErrorResponse, StatusMessage, USER_TAG},
+            paginated,
             user::{
-                user_payload::{DeleteUserByIDPayload, UserByIDPayload},
-                user_presenters::UserPresenter,
+                user_payload::{DeleteAccountPayload, DeleteUserByIDPayload, ListUsersQuery, UpdatePrivacyPayload, UserByIDPayload},
+                user_presenters::{AccountExportPresenter, DeletedUserPresenter, PublicUserPresenter, UserPresenter, UserView},
             },
         },
         ctx::Ctx,
     },
-    application::{AppError, AppResult, dto::user::GetUserByIdDTO},
+    application::{AppError, AppResult, dto::user::{DeleteUserDTO, GetUserByIdDTO, ListUsersDTO, UpdatePrivacyDTO}},
 };
 
 use super::user_payload::NewUserPayload;
 
-pub fn user_router() -> Router<AppState> {
-    let router = Router::new()
-        .route("/api/admin/user", delete(delete_user))
+pub fn user_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .routes(routes!(delete_user))
+        .routes(routes!(get_users))
+        .routes(routes!(get_deleted_users))
+        .routes(routes!(restore_user))
+        .route_layer(middleware::from_fn(middlewares::require_admin))
+        .routes(routes!(get_user, delete_own_account))
+        .routes(routes!(export_account))
+        .routes(routes!(update_privacy))
+        .routes(routes!(get_public_users))
+        .routes(routes!(get_user_by_id))
+        .route_layer(middleware::from_fn(middlewares::require_auth))
+        .routes(routes!(add_new_user))
+}
+
+/// Pre-v1 routes taking ids in JSON bodies, kept until clients migrate.
+/// Admin access under `/api/admin` is enforced by the context resolver.
+pub fn legacy_user_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .route("/api/admin/user", delete(legacy_delete_user))
+        .route("/api/users", get(legacy_get_users))
+        .route_layer(middleware::from_fn(middlewares::require_admin))
         .route("/api/user", get(get_user))
-        .route("/api/user-by-id", post(get_user_by_id))
-        .route("/api/users", get(get_users))
+        .route("/api/user-by-id", post(legacy_get_user_by_id))
         .route_layer(middleware::from_fn(middlewares::require_auth))
-        .route("/api/user", post(add_new_user));
+        .route("/api/user", post(add_new_user))
+        .layer(middleware::map_response(middlewares::mark_deprecated))
+}
 
-    router
+/// Account closing and export under the paths they were introduced with;
+/// these are new, so not deprecated like the rest of `/api/user`.
+pub fn user_account_router() -> OpenApiRouter<AppState> {
+    OpenApiRouter::new()
+        .route("/api/user", delete(delete_own_account))
+        .route("/api/user/export", get(export_account))
+        .route_layer(middleware::from_fn(middlewares::require_auth))
 }
 
+/// Register a new account
+#[utoipa::path(
+    post,
+    path = "/api/v1/users",
+    tag = USER_TAG,
+    request_body = NewUserPayload,
+    responses(
+        (status = 200, body = UserPresenter),
+        (status = 409, description = "`EMAIL_TAKEN`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
 async fn add_new_user(
     State(app_state): State<AppState>,
-    Json(payload): Json<NewUserPayload>,
+    ValidatedJson(payload): ValidatedJson<NewUserPayload>,
 ) -> AppResult<Json<UserPresenter>> {
-    let user_response = app_state.use_cases.create_user(payload.into()).await?;
+    let user_response = app_state.use_cases.create_user(payload.try_into()?).await?;
 
     Ok(Json(user_response.into()))
 }
 
+/// Profile of the signed-in user
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me",
+    tag = USER_TAG,
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = UserPresenter),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
 async fn get_user(State(app_state): State<AppState>, ctx: Ctx) -> AppResult<Json<UserPresenter>> {
     let user_id = ctx.get_user_id().to_owned();
 
@@ -58,20 +117,193 @@ async fn get_user(State(app_state): State<AppState>, ctx: Ctx) -> AppResult<Json
     Ok(Json(user_response.into()))
 }
 
-async fn get_user_by_id(
+/// Close the signed-in user's account; data is anonymised or erased
+/// according to the server's retention policy
+///
+/// Also served at `/api/user`.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/me",
+    tag = USER_TAG,
+    request_body = DeleteAccountPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = StatusMessage),
+        (status = 403, description = "`NO_AUTH` or `PASSWORD_MISMATCH`", body = ErrorResponse),
+        (status = 409, description = "`OWNER_CANNOT_LEAVE`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn delete_own_account(
     State(app_state): State<AppState>,
-    Json(payload): Json<UserByIDPayload>,
+    ctx: Ctx,
+    cookies: Cookies,
+    ValidatedJson(payload): ValidatedJson<DeleteAccountPayload>,
+) -> AppResult<Json<Value>> {
+    app_state
+        .use_cases
+        .delete_own_account(ctx.get_user_id(), payload.password.expose_secret().to_string())
+        .await?;
+
+    cookies.remove(app_state.cookies.removal_cookie(AUTH_TOKEN));
+    cookies.remove(app_state.cookies.removal_cookie(CSRF_TOKEN));
+
+    Ok(Json(json!(
+        {
+            "message": "account closed",
+            "status": "ok",
+        }
+    )))
+}
+
+/// Download everything stored about the signed-in user
+///
+/// Also served at `/api/user/export`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/export",
+    tag = USER_TAG,
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = AccountExportPresenter, content_type = "application/json"),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn export_account(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+) -> AppResult<([(header::HeaderName, &'static str); 1], Json<AccountExportPresenter>)> {
+    let export = app_state.use_cases.export_account(ctx.get_user_id()).await?;
+
+    Ok((
+        [(header::CONTENT_DISPOSITION, "attachment; filename=\"account-export.json\"")],
+        Json(export.into()),
+    ))
+}
+
+/// Privacy settings of the signed-in user
+#[utoipa::path(
+    patch,
+    path = "/api/v1/users/me/privacy",
+    tag = USER_TAG,
+    request_body = UpdatePrivacyPayload,
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = UserPresenter),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn update_privacy(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    ValidatedJson(payload): ValidatedJson<UpdatePrivacyPayload>,
 ) -> AppResult<Json<UserPresenter>> {
+    let user_dto = UpdatePrivacyDTO {
+        id: ctx.get_user_id().to_owned(),
+        show_email: payload.show_email,
+        show_role: payload.show_role,
+    };
+
+    let user_response = app_state.use_cases.update_privacy(user_dto).await?;
+
+    Ok(Json(user_response.into()))
+}
+
+/// Look up a user by id; email and status only for the user themselves, admins,
+/// or as allowed by the user's privacy settings
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{id}",
+    tag = USER_TAG,
+    params(("id" = Uuid, Path, description = "User id")),
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = UserView),
+        (status = 400, description = "`INVALID_PARAMS`", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn get_user_by_id(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Json<UserView>> {
+    let user_response = app_state
+        .use_cases
+        .get_user_by_id(GetUserByIdDTO { id: id.to_string() })
+        .await
+        .map_err(|_| AppError::UserNotFoundByID)?;
+
+    Ok(Json(UserView::for_viewer(&ctx, user_response)))
+}
+
+async fn legacy_get_user_by_id(
+    State(app_state): State<AppState>,
+    ctx: Ctx,
+    ValidatedJson(payload): ValidatedJson<UserByIDPayload>,
+) -> AppResult<Json<UserView>> {
     let user_response = app_state
         .use_cases
         .get_user_by_id(payload.into())
         .await
         .map_err(|_| AppError::UserNotFoundByID)?;
 
-    Ok(Json(user_response.into()))
+    Ok(Json(UserView::for_viewer(&ctx, user_response)))
+}
+
+/// List users with emails and status (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/users",
+    tag = USER_TAG,
+    params(ListUsersQuery),
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<UserPresenter>, headers(
+            ("x-total-count" = i64, description = "Matching users across all pages"),
+            ("x-next-cursor" = String, description = "Pass as `cursor` for the next page; absent on the last page"),
+        )),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn get_users(
+    State(app_state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<ListUsersQuery>,
+) -> AppResult<(HeaderMap, Json<Vec<UserPresenter>>)> {
+    let page = app_state.use_cases.list_users(query.try_into()?).await?;
+
+    Ok(paginated(page.map(UserPresenter::from)))
 }
 
-async fn get_users(State(app_state): State<AppState>) -> AppResult<Json<Vec<UserPresenter>>> {
+/// Directory of active users, showing only what each user has made visible
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/public",
+    tag = USER_TAG,
+    params(ListUsersQuery),
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<PublicUserPresenter>, headers(
+            ("x-total-count" = i64, description = "Matching users across all pages"),
+            ("x-next-cursor" = String, description = "Pass as `cursor` for the next page; absent on the last page"),
+        )),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
+async fn get_public_users(
+    State(app_state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<ListUsersQuery>,
+) -> AppResult<(HeaderMap, Json<Vec<PublicUserPresenter>>)> {
+    let page = app_state.use_cases.list_public_users(ListUsersDTO::try_from(query)?).await?;
+
+    Ok(paginated(page.map(PublicUserPresenter::from)))
+}
+
+async fn legacy_get_users(State(app_state): State<AppState>) -> AppResult<Json<Vec<UserPresenter>>> {
     let users = app_state.use_cases.get_users().await?;
 
     let response = users.into_iter().map(|u| u.into()).collect();
@@ -79,9 +311,78 @@ async fn get_users(State(app_state): State<AppState>) -> AppResult<Json<Vec<User
     Ok(Json(response))
 }
 
+/// Delete a user (admin only); restorable until the retention window ends
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{id}",
+    tag = USER_TAG,
+    params(("id" = Uuid, Path, description = "User id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = StatusMessage),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 422, description = "`VALIDATION_ERROR`", body = ErrorResponse),
+    )
+)]
 async fn delete_user(
     State(app_state): State<AppState>,
-    Json(payload): Json<DeleteUserByIDPayload>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Json<Value>> {
+    app_state.use_cases.delete_user_by_id(DeleteUserDTO { id: id.to_string() }).await?;
+
+    Ok(Json(json!(
+        {
+            "message": "user deleted",
+            "status": "ok",
+        }
+    )))
+}
+
+/// Users deleted within the retention window (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/deleted",
+    tag = USER_TAG,
+    security(("cookie_auth" = [])),
+    responses(
+        (status = 200, body = Vec<DeletedUserPresenter>),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+    )
+)]
+async fn get_deleted_users(State(app_state): State<AppState>) -> AppResult<Json<Vec<DeletedUserPresenter>>> {
+    let retention = app_state.use_cases.retention();
+
+    let users = app_state.use_cases.get_deleted_users().await?;
+
+    Ok(Json(users.into_iter().map(|u| DeletedUserPresenter::new(u, retention)).collect()))
+}
+
+/// Undo a deletion within the retention window (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{id}/restore",
+    tag = USER_TAG,
+    params(("id" = Uuid, Path, description = "User id")),
+    security(("cookie_auth" = [], "csrf_token" = [])),
+    responses(
+        (status = 200, body = UserPresenter),
+        (status = 400, description = "`INVALID_PARAMS`: not deleted, or already purged", body = ErrorResponse),
+        (status = 403, description = "`NO_AUTH`", body = ErrorResponse),
+        (status = 409, description = "`EMAIL_TAKEN` by an account created since", body = ErrorResponse),
+    )
+)]
+async fn restore_user(
+    State(app_state): State<AppState>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> AppResult<Json<UserPresenter>> {
+    let user = app_state.use_cases.restore_user(id.to_string()).await?;
+
+    Ok(Json(user.into()))
+}
+
+async fn legacy_delete_user(
+    State(app_state): State<AppState>,
+    ValidatedJson(payload): ValidatedJson<DeleteUserByIDPayload>,
 ) -> AppResult<Json<Value>> {
     app_state.use_cases.delete_user_by_id(payload.into()).await?;
 
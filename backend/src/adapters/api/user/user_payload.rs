@@ -1,26 +1,177 @@
-use secrecy::SecretString;
+use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize};
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Debug, Deserialize)]
+use crate::{application::{dto::user::UserSort, pagination::{Cursor, MAX_PAGE_SIZE, SortOrder}, validation::{Validate, ValidationErrors}}, domain::entities::{user::{UserRole, UserStatus}, value_objects::{DisplayName, Email}}};
+
+pub const PASSWORD_MIN_LEN: usize = 8;
+pub const PASSWORD_MAX_LEN: usize = 128;
+
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct NewUserPayload {
     pub name: String,
     pub email: String,
+    #[schema(value_type = String, format = Password)]
     pub password: SecretString,
 }
 
-#[derive(Debug, Deserialize)]
+impl Validate for NewUserPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if errors.required("name", &self.name) {
+            errors.max_len("name", &self.name, DisplayName::MAX_LEN);
+        }
+
+        if errors.required("email", &self.email) {
+            errors.email("email", &self.email);
+            errors.max_len("email", &self.email, Email::MAX_LEN);
+        }
+
+        let password = self.password.expose_secret();
+        errors.min_len("password", password, PASSWORD_MIN_LEN);
+        errors.max_len("password", password, PASSWORD_MAX_LEN);
+
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct LoginUserPayload {
     pub email: String,
+    #[schema(value_type = String, format = Password)]
     pub password: SecretString,
 }
 
-#[derive(Debug, Deserialize)]
+impl Validate for LoginUserPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.required("email", &self.email);
+        errors.required("password", self.password.expose_secret());
+
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UserByIDPayload {
     pub id: String,
 }
 
+impl Validate for UserByIDPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
 
-#[derive(Debug, Deserialize)]
+        errors.uuid("id", &self.id);
+
+        errors.into_result()
+    }
+}
+
+
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct DeleteUserByIDPayload {
     pub id: String,
-}
\ No newline at end of file
+}
+
+impl Validate for DeleteUserByIDPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.uuid("id", &self.id);
+
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListUsersQuery {
+    /// Page size, 1 to 100 (default 20)
+    pub limit: Option<u32>,
+    /// `X-Next-Cursor` of the previous page
+    pub cursor: Option<String>,
+    /// Case-insensitive prefix of the name (or email, for admins)
+    pub q: Option<String>,
+    /// `admin` or `user`
+    pub role: Option<String>,
+    /// `active`, `suspended` or `closed`
+    pub status: Option<String>,
+    /// `name`, `email` or `created_at` (default)
+    pub sort: Option<String>,
+    /// `asc` (default) or `desc`
+    pub order: Option<String>,
+}
+
+impl Validate for ListUsersQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(limit) = self.limit {
+            errors.range("limit", limit, 1, MAX_PAGE_SIZE);
+        }
+
+        if let Some(q) = &self.q {
+            errors.max_len("q", q, Email::MAX_LEN);
+        }
+
+        if let Some(role) = &self.role {
+            errors.parse::<UserRole>("role", role);
+        }
+
+        if let Some(status) = &self.status {
+            errors.parse::<UserStatus>("status", status);
+        }
+
+        if let Some(order) = &self.order {
+            errors.parse::<SortOrder>("order", order);
+        }
+
+        let sort = match &self.sort {
+            Some(sort) => errors.parse::<UserSort>("sort", sort),
+            None => Some(UserSort::default()),
+        };
+
+        if let (Some(cursor), Some(sort)) = (&self.cursor, sort)
+            && let Err(e) = Cursor::decode(cursor, sort.as_str())
+        {
+            errors.extend(e);
+        }
+
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePrivacyPayload {
+    pub show_email: Option<bool>,
+    pub show_role: Option<bool>,
+}
+
+impl Validate for UpdatePrivacyPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        if self.show_email.is_none() && self.show_role.is_none() {
+            return Err(ValidationErrors::single("body", "required", "at least one setting must be given"));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DeleteAccountPayload {
+    /// Current password, re-entered to confirm
+    #[schema(value_type = String, format = Password)]
+    pub password: SecretString,
+}
+
+impl Validate for DeleteAccountPayload {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        errors.required("password", self.password.expose_secret());
+
+        errors.into_result()
+    }
+}
@@ -1,13 +1,23 @@
+use chrono::TimeDelta;
 use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
 
-use crate::application::dto::user::ResponseUserDTO;
+use crate::{
+    adapters::{api::{chat::chat_presenter::ChatPresenter, notification::notification_presenter::NotificationPresenter, submission::submission_presenter::SubmissionPresenter}, ctx::Ctx},
+    application::{dto::{chat::MembershipDTO, user::{ResponseUserDTO, UserExportDTO}}, retention::Deleted},
+    domain::entities::user::{PrivacySettings, UserRole},
+};
 
-#[derive(Debug, Serialize)]
+/// Full profile, shown to the user themselves and to admins.
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UserPresenter {
     id: String,
     email: String,
     display_name: String,
     role: String,
+    status: String,
+    privacy: PrivacyPresenter,
 }
 
 impl From<ResponseUserDTO> for UserPresenter {
@@ -17,6 +27,188 @@ impl From<ResponseUserDTO> for UserPresenter {
             email: value.email,
             display_name: value.name,
             role: value.role,
+            status: value.status,
+            privacy: value.privacy.into(),
         }
     }
 }
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PrivacyPresenter {
+    show_email: bool,
+    show_role: bool,
+}
+
+impl From<PrivacySettings> for PrivacyPresenter {
+    fn from(value: PrivacySettings) -> Self {
+        Self {
+            show_email: value.show_email,
+            show_role: value.show_role,
+        }
+    }
+}
+
+/// What any signed-in user may see about others; optional fields only when
+/// the owner's privacy settings allow it.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicUserPresenter {
+    id: String,
+    display_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+}
+
+impl From<ResponseUserDTO> for PublicUserPresenter {
+    fn from(value: ResponseUserDTO) -> Self {
+        Self {
+            id: value.id,
+            display_name: value.name,
+            role: value.privacy.show_role.then_some(value.role),
+            email: value.privacy.show_email.then_some(value.email),
+        }
+    }
+}
+
+/// Entry of the admin trash listing.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeletedUserPresenter {
+    #[serde(flatten)]
+    user: UserPresenter,
+    deleted_at: String,
+    /// Purged after this moment
+    restorable_until: String,
+}
+
+impl DeletedUserPresenter {
+    pub fn new(deleted: Deleted<ResponseUserDTO>, retention: TimeDelta) -> Self {
+        Self {
+            deleted_at: deleted.deleted_at.to_rfc3339(),
+            restorable_until: deleted.restorable_until(retention).to_rfc3339(),
+            user: deleted.item.into(),
+        }
+    }
+}
+
+/// Archive handed out by the data export endpoint.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountExportPresenter {
+    exported_at: String,
+    profile: UserPresenter,
+    created_at: String,
+    memberships: Vec<MembershipPresenter>,
+    submissions: Vec<SubmissionPresenter>,
+    notifications: Vec<NotificationPresenter>,
+    owned_chats: Vec<ChatPresenter>,
+    /// Chat ids, most recently added first
+    favorites: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MembershipPresenter {
+    chat_id: Uuid,
+    chat_name: String,
+    /// `owner`, `moderator` or `member`
+    role: String,
+    joined_at: String,
+}
+
+impl From<MembershipDTO> for MembershipPresenter {
+    fn from(value: MembershipDTO) -> Self {
+        Self {
+            chat_id: value.chat_id,
+            chat_name: value.chat_name,
+            role: value.role.as_str().to_string(),
+            joined_at: value.joined_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<UserExportDTO> for AccountExportPresenter {
+    fn from(value: UserExportDTO) -> Self {
+        Self {
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            profile: value.profile.into(),
+            created_at: value.created_at.to_rfc3339(),
+            memberships: value.memberships.into_iter().map(Into::into).collect(),
+            submissions: value.submissions.into_iter().map(Into::into).collect(),
+            notifications: value.notifications.into_iter().map(Into::into).collect(),
+            owned_chats: value.owned_chats.into_iter().map(Into::into).collect(),
+            favorites: value.favorites,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Audience {
+    Owner,
+    Admin,
+    Public,
+}
+
+impl Audience {
+    pub fn of(ctx: &Ctx, user_id: &str) -> Self {
+        if ctx.get_user_id() == user_id {
+            Audience::Owner
+        } else if ctx.get_user_role() == UserRole::Admin.as_str() {
+            Audience::Admin
+        } else {
+            Audience::Public
+        }
+    }
+}
+
+/// A single user as seen by the caller.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum UserView {
+    Full(UserPresenter),
+    Public(PublicUserPresenter),
+}
+
+impl UserView {
+    pub fn for_viewer(ctx: &Ctx, user: ResponseUserDTO) -> Self {
+        match Audience::of(ctx, &user.id) {
+            Audience::Owner | Audience::Admin => UserView::Full(user.into()),
+            Audience::Public => UserView::Public(user.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(privacy: PrivacySettings) -> ResponseUserDTO {
+        ResponseUserDTO::new(
+            "u1".to_string(),
+            "Anna".to_string(),
+            "anna@example.org".to_string(),
+            "user".to_string(),
+            "active".to_string(),
+            privacy,
+        )
+    }
+
+    #[test]
+    fn test_email_only_reaches_owner_admin_or_by_consent() {
+        let owner = Ctx::new("u1".to_string(), "user".to_string());
+        let admin = Ctx::new("a1".to_string(), "admin".to_string());
+        let other = Ctx::new("u2".to_string(), "user".to_string());
+
+        let emails = |ctx: &Ctx, privacy| {
+            let json = serde_json::to_value(UserView::for_viewer(ctx, user(privacy))).unwrap();
+
+            json.get("email").is_some()
+        };
+
+        let private = PrivacySettings::default();
+        let shared = PrivacySettings { show_email: true, show_role: false };
+
+        assert!(emails(&owner, private));
+        assert!(emails(&admin, private));
+        assert!(!emails(&other, private));
+        assert!(emails(&other, shared));
+    }
+}
@@ -8,6 +8,12 @@ use crate::{adapters::crypto::errors::{CryptoError, CryptoResult}, application::
 
 pub struct ArgonHasher {}
 
+impl Default for ArgonHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ArgonHasher {
     pub fn new() -> Self {
 
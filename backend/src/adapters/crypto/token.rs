@@ -48,8 +48,10 @@ impl FromStr for Token {
     }
 }
 
+pub const TOKEN_TTL_SECS: i64 = 1800;
+
 pub async fn generate_token(hasher: Arc<dyn Hasher>, ident: &str) -> AppResult<Token> {
-    let exp = now_utc_plus_sec_str(1800);
+    let exp = now_utc_plus_sec_str(TOKEN_TTL_SECS);
 
     let sign = hasher
         .hash(format!("{}.{}", b64_encode(ident), b64_encode(&exp)))
@@ -59,8 +61,8 @@ pub async fn generate_token(hasher: Arc<dyn Hasher>, ident: &str) -> AppResult<T
 
     Ok(Token {
         ident: ident.to_string(),
-        exp: exp,
-        sign_b64u: sign_b64u,
+        exp,
+        sign_b64u,
     })
 }
 
@@ -1,6 +1,10 @@
 use axum::extract::FromRequestParts;
 
-use crate::application::{AppError, AppResult};
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::{application::{AppError, AppResult}, domain::entities::user::{Actor, UserRole}};
 
 #[derive(Debug, Clone)]
 pub struct Ctx {
@@ -36,12 +40,23 @@ impl<S:Send + Sync> FromRequestParts<S> for Ctx {
             .get::<CtxResult>()
             .ok_or(CtxError::CtxNotInRequest)?
             .clone()
-            .map_err(|e| AppError::Context(e))
+            .map_err(AppError::Context)
 
     }
 
 }
 
+impl TryFrom<&Ctx> for Actor {
+    type Error = AppError;
+
+    fn try_from(value: &Ctx) -> AppResult<Self> {
+        Ok(Self {
+            user_id: Uuid::from_str(value.get_user_id())?,
+            role: UserRole::from_str(value.get_user_role())?,
+        })
+    }
+}
+
 pub type CtxResult = Result<Ctx, CtxError>;
 
 
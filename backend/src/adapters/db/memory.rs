@@ -0,0 +1,13 @@
+pub mod favorite;
+
+use std::{collections::HashMap, sync::RwLock};
+
+use uuid::Uuid;
+
+/// Favourites kept in process memory; lost on restart. Stands in where no
+/// database is wired up, e.g. in tests.
+#[derive(Default)]
+pub struct InMemoryFavoriteRepo {
+    /// Chat ids per user, oldest first
+    favorites: RwLock<HashMap<Uuid, Vec<Uuid>>>,
+}
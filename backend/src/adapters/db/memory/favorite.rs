@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{adapters::db::memory::InMemoryFavoriteRepo, application::{AppError, AppResult, repositories::favorite::FavoriteRepo}};
+
+#[async_trait]
+impl FavoriteRepo for InMemoryFavoriteRepo {
+    async fn add_favorite(&self, user_id: Uuid, chat_id: Uuid) -> AppResult<()> {
+        let mut favorites = self.favorites.write().map_err(|_| AppError::Internal)?;
+        let chats = favorites.entry(user_id).or_default();
+
+        if !chats.contains(&chat_id) {
+            chats.push(chat_id);
+        }
+
+        Ok(())
+    }
+
+    async fn remove_favorite(&self, user_id: Uuid, chat_id: Uuid) -> AppResult<()> {
+        let mut favorites = self.favorites.write().map_err(|_| AppError::Internal)?;
+
+        if let Some(chats) = favorites.get_mut(&user_id) {
+            chats.retain(|c| *c != chat_id);
+        }
+
+        Ok(())
+    }
+
+    async fn get_favorites(&self, user_id: Uuid) -> AppResult<Vec<Uuid>> {
+        let favorites = self.favorites.read().map_err(|_| AppError::Internal)?;
+
+        Ok(favorites.get(&user_id).map(|chats| chats.iter().rev().copied().collect()).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_favorites_are_per_user_newest_first_and_idempotent() {
+        let repo = InMemoryFavoriteRepo::default();
+        let (user, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+        repo.add_favorite(user, first).await.unwrap();
+        repo.add_favorite(user, second).await.unwrap();
+        repo.add_favorite(user, first).await.unwrap();
+
+        assert_eq!(repo.get_favorites(user).await.unwrap(), vec![second, first]);
+        assert!(repo.get_favorites(other).await.unwrap().is_empty());
+
+        repo.remove_favorite(user, second).await.unwrap();
+        repo.remove_favorite(user, second).await.unwrap();
+
+        assert_eq!(repo.get_favorites(user).await.unwrap(), vec![first]);
+    }
+}
@@ -1,6 +1,12 @@
 pub mod user;
 pub mod errors;
 pub mod chat;
+pub mod location;
+pub mod tag;
+pub mod submission;
+pub mod notification;
+pub mod link_check;
+pub mod favorite;
 
 use sqlx::PgPool;
 
@@ -22,4 +28,64 @@ impl PostgresChatRepo {
     pub fn new(pool: PgPool) -> Self {
         Self{ pool }
     }
-}
\ No newline at end of file
+}
+
+pub struct PostgresLocationRepo {
+    pool: PgPool
+}
+
+impl PostgresLocationRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self{ pool }
+    }
+}
+
+pub struct PostgresTagRepo {
+    pool: PgPool
+}
+
+impl PostgresTagRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self{ pool }
+    }
+}
+
+pub struct PostgresSubmissionRepo {
+    pool: PgPool
+}
+
+impl PostgresSubmissionRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self{ pool }
+    }
+}
+
+pub struct PostgresNotificationRepo {
+    pool: PgPool
+}
+
+impl PostgresNotificationRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self{ pool }
+    }
+}
+
+pub struct PostgresLinkCheckRepo {
+    pool: PgPool
+}
+
+impl PostgresLinkCheckRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self{ pool }
+    }
+}
+
+pub struct PostgresFavoriteRepo {
+    pool: PgPool
+}
+
+impl PostgresFavoriteRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self{ pool }
+    }
+}
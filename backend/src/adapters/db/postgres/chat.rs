@@ -1,10 +1,17 @@
 use async_trait::async_trait;
-use sqlx::query_as;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgConnection, Postgres, QueryBuilder};
 use uuid::Uuid;
 use std::str::FromStr;
 
 
-use crate::{adapters::{api::chat::chat_controller::CreateChatPayload, db::postgres::PostgresChatRepo}, application::{AppResult, repositories::chat::ChatRepo}, domain::entities::chat::Chat};
+use crate::{adapters::db::postgres::{PostgresChatRepo, errors::{CorruptRow, is_foreign_key_violation, map_unique_violation}}, application::{AppError, AppResult, dto::chat::{ChatFacetsDTO, ChatFilterDTO, CreateChatDTO, FollowedChatDTO, LinkReportDTO, ListMembersDTO, MemberDTO, MembershipDTO, NearbyChatDTO, NearbyChatsDTO, UpdateChatDTO}, errors::Conflict, pagination::{Cursor, Page}, repositories::chat::ChatRepo, retention::Deleted}, domain::entities::{chat::{Chat, ChatCategory}, member::{ChatMember, MemberRole}, value_objects::{ChatName, GeoPoint, InviteLink, Location, MemberCount, TagName}}};
+
+/// Columns of [`ChatDB`], for queries assembled at runtime.
+const CHAT_COLUMNS: &str = "id, name, location, description, latitude, longitude, location_id, category, invite_link,
+    (select m.user_id from chat_members m where m.chat_id = chats.id and m.role = 'owner') as owner_id,
+    greatest((select count(*) from chat_members m join users u on u.id = m.user_id where m.chat_id = chats.id and u.deleted_at is null), chats.legacy_users_count) as users_count,
+    array(select t.name from chat_tags ct join tags t on t.id = ct.tag_id where ct.chat_id = chats.id order by t.name) as tags";
 
 #[derive(Debug, sqlx::FromRow)]
 struct ChatDB{
@@ -13,62 +20,621 @@ struct ChatDB{
     users_count: i64,
     location: String,
     description: String,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    location_id: Option<Uuid>,
+    category: String,
+    tags: Vec<String>,
+    owner_id: Option<Uuid>,
+    invite_link: Option<String>,
+}
+
+/// Lifts a `query!` record carrying the chat columns into a [`ChatDB`].
+macro_rules! chat_db {
+    ($row:expr) => {
+        ChatDB {
+            id: $row.id,
+            name: $row.name,
+            users_count: $row.users_count,
+            location: $row.location,
+            description: $row.description,
+            latitude: $row.latitude,
+            longitude: $row.longitude,
+            location_id: $row.location_id,
+            category: $row.category,
+            tags: $row.tags,
+            owner_id: $row.owner_id,
+            invite_link: $row.invite_link,
+        }
+    };
+}
+
+impl TryFrom<ChatDB> for Chat {
+    type Error = CorruptRow;
+
+    fn try_from(value: ChatDB) -> Result<Self, Self::Error> {
+        Ok(Chat::new(
+            Some(value.id),
+            ChatName::from_stored(value.name),
+            value.description,
+            MemberCount::try_from(value.users_count)?,
+            Location::from_stored(value.location),
+        )
+        .with_position(match (value.latitude, value.longitude) {
+            (Some(lat), Some(lon)) => Some(GeoPoint::new(lat, lon)?),
+            _ => None,
+        })
+        .with_location_id(value.location_id)
+        .with_category(value.category.parse()?)
+        .with_tags(value.tags.iter().map(|t| TagName::parse(t)).collect::<Result<_, _>>()?)
+        .with_owner_id(value.owner_id)
+        .with_invite_link(value.invite_link.as_deref().map(InviteLink::parse).transpose()?))
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MemberRow {
+    chat_id: Uuid,
+    user_id: Uuid,
+    role: String,
+}
+
+impl TryFrom<MemberRow> for ChatMember {
+    type Error = CorruptRow;
+
+    fn try_from(value: MemberRow) -> Result<Self, Self::Error> {
+        Ok(ChatMember::new(value.chat_id, value.user_id, value.role.parse()?))
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MemberPageRow {
+    user_id: Uuid,
+    display_name: String,
+    role: String,
+    joined_at: DateTime<Utc>,
+    sort_key: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct MembershipRow {
+    chat_id: Uuid,
+    chat_name: String,
+    role: String,
+    joined_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct FollowedChatRow {
+    chat_id: Uuid,
+    name: String,
+    clicks: i64,
+    last_followed_on: NaiveDate,
+}
+
+impl From<FollowedChatRow> for FollowedChatDTO {
+    fn from(value: FollowedChatRow) -> Self {
+        Self {
+            chat_id: value.chat_id,
+            name: value.name,
+            clicks: value.clicks,
+            last_followed_on: value.last_followed_on,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CountRow {
+    name: String,
+    count: i64,
+}
+
+/// Narrows `chats` to the filter; the subtree CTE must already be pushed
+/// when a location is given.
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, filter: &ChatFilterDTO) {
+    if filter.location_id.is_some() {
+        builder.push(" and chats.location_id in (select id from subtree)");
+    }
 
+    if let Some(category) = filter.category {
+        builder.push(" and chats.category = ").push_bind(category.as_str());
+    }
+
+    if !filter.tags.is_empty() {
+        let names: Vec<String> = filter.tags.iter().map(|t| t.as_str().to_string()).collect();
+
+        builder
+            .push(" and (select count(*) from chat_tags ct join tags t on t.id = ct.tag_id where ct.chat_id = chats.id and t.name = any(")
+            .push_bind(names)
+            .push(")) = ")
+            .push_bind(filter.tags.len() as i64);
+    }
 }
 
-impl From<ChatDB> for Chat {
-    fn from(value: ChatDB) -> Self {
-        Chat::new(Some(value.id), value.name, value.description, value.users_count as u64, value.location)
+fn filtered(filter: &ChatFilterDTO, select: &str) -> QueryBuilder<'static, Postgres> {
+    let mut builder = QueryBuilder::<Postgres>::new("");
+
+    if let Some(location_id) = filter.location_id {
+        builder
+            .push(
+                "with recursive subtree as (
+                    select id from locations where id = ",
+            )
+            .push_bind(location_id)
+            .push(
+                " union all
+                    select l.id from locations l join subtree s on l.parent_id = s.id
+                ) ",
+            );
     }
+
+    builder.push(select).push(" where chats.deleted_at is null");
+    push_filters(&mut builder, filter);
+
+    builder
+}
+
+/// Replaces the tags of a chat inside the caller's transaction.
+async fn replace_tags(conn: &mut PgConnection, chat_id: Uuid, tags: &[TagName]) -> AppResult<()> {
+    let names: Vec<&str> = tags.iter().map(TagName::as_str).collect();
+
+    sqlx::query!("delete from chat_tags where chat_id = $1", chat_id)
+        .execute(&mut *conn)
+        .await?;
+
+    let inserted = sqlx::query!("insert into chat_tags(chat_id, tag_id) select $1, id from tags where name = any($2)", chat_id, &names as &[&str])
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+    if inserted != names.len() as u64 {
+        return Err(AppError::TagNotFound);
+    }
+
+    Ok(())
+}
+
+/// Lists a chat with its tags; also used when approving a submission.
+pub(super) async fn insert_chat(conn: &mut PgConnection, chat_id: Uuid, chat_dto: CreateChatDTO) -> AppResult<Chat> {
+    sqlx::query!(
+        "insert into chats(id, name, location, description, latitude, longitude, location_id, category, invite_link)
+        values ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        chat_id,
+        chat_dto.name.as_str(),
+        chat_dto.location.as_str(),
+        chat_dto.description,
+        chat_dto.position.map(|p| p.lat()),
+        chat_dto.position.map(|p| p.lon()),
+        chat_dto.location_id,
+        chat_dto.category.as_str(),
+        chat_dto.invite_link.as_ref().map(InviteLink::as_str),
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| if is_foreign_key_violation(&e) { AppError::LocationNotFound } else { map_unique_violation(e, Conflict::ChatNameTaken) })?;
+
+    replace_tags(conn, chat_id, &chat_dto.tags).await?;
+
+    select_chat(conn, chat_id).await
+}
+
+async fn select_chat(conn: &mut PgConnection, chat_id: Uuid) -> AppResult<Chat> {
+    let chat = sqlx::query_as!(
+        ChatDB,
+        r#"select id, name, location, description, latitude, longitude, location_id, category, invite_link,
+                (select m.user_id from chat_members m where m.chat_id = chats.id and m.role = 'owner') as owner_id,
+                greatest((select count(*) from chat_members m join users u on u.id = m.user_id where m.chat_id = chats.id and u.deleted_at is null), chats.legacy_users_count) as "users_count!",
+                array(select t.name from chat_tags ct join tags t on t.id = ct.tag_id where ct.chat_id = chats.id order by t.name) as "tags!"
+        from chats where id = $1 and deleted_at is null"#,
+        chat_id,
+    )
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or(AppError::ChatNotFound)?;
+
+    Ok(chat.try_into()?)
 }
 
 #[async_trait]
 impl ChatRepo for PostgresChatRepo {
-    async fn add_chat(&self, payload: CreateChatPayload) -> AppResult<Chat> {
-        let chat = sqlx::query_as!(
+    async fn add_chat(&self, chat_dto: CreateChatDTO) -> AppResult<Chat> {
+        let mut tx = self.pool.begin().await?;
+
+        let chat = insert_chat(&mut tx, Uuid::new_v4(), chat_dto).await?;
+
+        tx.commit().await?;
+
+        Ok(chat)
+    }
+
+    async fn get_chats(&self, filter: ChatFilterDTO) -> AppResult<Vec<Chat>> {
+        let chats_from_db = filtered(&filter, &format!("select {CHAT_COLUMNS} from chats"))
+            .push(" order by chats.name")
+            .build_query_as::<ChatDB>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        let chats = chats_from_db
+            .into_iter()
+            .map(Chat::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(chats)
+    }
+
+    async fn get_chat(&self, id: Uuid) -> AppResult<Chat> {
+        let mut conn = self.pool.acquire().await?;
+
+        select_chat(&mut conn, id).await
+    }
+
+    async fn get_chats_by_ids(&self, ids: &[Uuid]) -> AppResult<Vec<Chat>> {
+        let rows = sqlx::query_as!(
             ChatDB,
-            r#"insert into chats(id,name,users_count,location,description)
-            values ($1, $2, $3, $4, $5)
-                returning id,name,users_count,location,description"#,
-            Uuid::new_v4(),
-            payload.name,
-            payload.users_count,
-            payload.location,
-            payload.description,
+            r#"select id, name, location, description, latitude, longitude, location_id, category, invite_link,
+                (select m.user_id from chat_members m where m.chat_id = chats.id and m.role = 'owner') as owner_id,
+                greatest((select count(*) from chat_members m join users u on u.id = m.user_id where m.chat_id = chats.id and u.deleted_at is null), chats.legacy_users_count) as "users_count!",
+                array(select t.name from chat_tags ct join tags t on t.id = ct.tag_id where ct.chat_id = chats.id order by t.name) as "tags!"
+            from chats where id = any($1) and deleted_at is null"#,
+            ids,
         )
-        .fetch_one(&self.pool)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let chats = rows
+            .into_iter()
+            .map(Chat::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(chats)
+    }
+
+    async fn update_chat(&self, chat_dto: UpdateChatDTO) -> AppResult<Chat> {
+        let mut tx = self.pool.begin().await?;
+
+        let invite_link = chat_dto.invite_link.as_ref().map(|link| link.as_ref().map(InviteLink::as_str));
+
+        let updated = sqlx::query!(
+            "update chats
+            set category = coalesce($2, category),
+                description = coalesce($3, description),
+                invite_link = case when $4 then $5 else invite_link end
+            where id = $1 and deleted_at is null",
+            chat_dto.id,
+            chat_dto.category.map(|c| c.as_str()),
+            chat_dto.description,
+            invite_link.is_some(),
+            invite_link.flatten(),
+        )
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        if updated == 0 {
+            return Err(AppError::ChatNotFound);
+        }
+
+        if let Some(tags) = &chat_dto.tags {
+            replace_tags(&mut tx, chat_dto.id, tags).await?;
+        }
+
+        let chat = select_chat(&mut tx, chat_dto.id).await?;
+
+        tx.commit().await?;
+
+        Ok(chat)
+    }
+
+    async fn get_facets(&self, filter: ChatFilterDTO) -> AppResult<ChatFacetsDTO> {
+        let categories = filtered(&filter, "select chats.category as name, count(*) as count from chats")
+            .push(" group by chats.category order by count desc, name")
+            .build_query_as::<CountRow>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        let tags = filtered(&filter, "select t.name, count(*) as count from chats join chat_tags ct on ct.chat_id = chats.id join tags t on t.id = ct.tag_id")
+            .push(" group by t.name order by count desc, t.name")
+            .build_query_as::<CountRow>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(ChatFacetsDTO {
+            categories: categories
+                .into_iter()
+                .map(|row| Ok((row.name.parse::<ChatCategory>()?, row.count)))
+                .collect::<Result<_, CorruptRow>>()?,
+            tags: tags
+                .into_iter()
+                .map(|row| Ok((TagName::parse(&row.name)?, row.count)))
+                .collect::<Result<_, CorruptRow>>()?,
+        })
+    }
+
+    async fn delete_chat_by_id(&self, id: String) -> AppResult<()> {
+        let chat_id = Uuid::from_str(&id)?;
+
+        let _res = sqlx::query!("update chats set deleted_at = now() where id = $1 and deleted_at is null", chat_id)
+        .execute(&self.pool)
         .await?;
 
-        Ok(chat.into())
+        Ok(())
     }
 
-    async fn get_chats(&self) -> AppResult<Vec<Chat>> {
+    async fn find_nearby(&self, query: NearbyChatsDTO) -> AppResult<Vec<NearbyChatDTO>> {
+        let (min_lat, max_lat, min_lon, max_lon) = query.center.bounding_box(query.radius_m);
 
-        let chats_from_db = query_as!(
-            ChatDB,
-            r#"
-            select *
-            from chats
-            "#
-        ).fetch_all(&self.pool).await?;
+        // Plain haversine so no PostGIS is needed; the bounding box lets the
+        // coordinates index discard most rows first
+        let rows = sqlx::query!(
+            r#"select id as "id!", name as "name!", location as "location!", description as "description!",
+                latitude, longitude, location_id, category as "category!", invite_link, owner_id,
+                users_count as "users_count!", tags as "tags!", distance_m as "distance_m!"
+            from (
+                select id, name, location, description, latitude, longitude, location_id, category, invite_link,
+                    (select m.user_id from chat_members m where m.chat_id = chats.id and m.role = 'owner') as owner_id,
+                    greatest((select count(*) from chat_members m join users u on u.id = m.user_id where m.chat_id = chats.id and u.deleted_at is null), chats.legacy_users_count) as users_count,
+                    array(select t.name from chat_tags ct join tags t on t.id = ct.tag_id where ct.chat_id = chats.id order by t.name) as tags,
+                    2 * $1::float8 * asin(least(1, sqrt(
+                        power(sin(radians(latitude - $2) / 2), 2)
+                        + cos(radians($2)) * cos(radians(latitude)) * power(sin(radians(longitude - $3) / 2), 2)
+                    ))) as distance_m
+                from chats
+                where deleted_at is null
+                    and latitude between $4 and $5
+                    and longitude between $6 and $7
+            ) candidates
+            where distance_m <= $8
+            order by distance_m, id
+            limit $9"#,
+            GeoPoint::EARTH_RADIUS_M,
+            query.center.lat(),
+            query.center.lon(),
+            min_lat,
+            max_lat,
+            min_lon,
+            max_lon,
+            query.radius_m,
+            i64::from(query.limit),
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-        let chats = chats_from_db.into_iter().map(|c| c.into()).collect();
+        let chats = rows
+            .into_iter()
+            .map(|row| Ok(NearbyChatDTO { distance_m: row.distance_m, chat: Chat::try_from(chat_db!(row))? }))
+            .collect::<Result<Vec<_>, CorruptRow>>()?;
 
-        
         Ok(chats)
     }
 
-    async fn delete_chat_by_id(&self, id: String) -> AppResult<()> {
-        let query = "DELETE FROM chats WHERE id = $1";
+    async fn get_deleted_chats(&self, since: DateTime<Utc>) -> AppResult<Vec<Deleted<Chat>>> {
+        let rows = sqlx::query!(
+            r#"select id, name, location, description, latitude, longitude, location_id, category, invite_link,
+                (select m.user_id from chat_members m where m.chat_id = chats.id and m.role = 'owner') as owner_id,
+                greatest((select count(*) from chat_members m join users u on u.id = m.user_id where m.chat_id = chats.id and u.deleted_at is null), chats.legacy_users_count) as "users_count!",
+                array(select t.name from chat_tags ct join tags t on t.id = ct.tag_id where ct.chat_id = chats.id order by t.name) as "tags!",
+                deleted_at as "deleted_at!"
+            from chats where deleted_at > $1 order by deleted_at desc"#,
+            since,
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
+        let chats = rows
+            .into_iter()
+            .map(|row| Ok(Deleted { deleted_at: row.deleted_at, item: Chat::try_from(chat_db!(row))? }))
+            .collect::<Result<Vec<_>, CorruptRow>>()?;
+
+        Ok(chats)
+    }
+
+    async fn restore_chat(&self, id: String, since: DateTime<Utc>) -> AppResult<Chat> {
         let chat_id = Uuid::from_str(&id)?;
 
-        let _res = sqlx::query(
-            query
-        ).bind(chat_id)
-        .execute(&self.pool)
+        let chat = sqlx::query_as!(
+            ChatDB,
+            r#"update chats set deleted_at = null
+            where id = $1 and deleted_at > $2
+                returning id, name, location, description, latitude, longitude, location_id, category, invite_link,
+                (select m.user_id from chat_members m where m.chat_id = chats.id and m.role = 'owner') as owner_id,
+                greatest((select count(*) from chat_members m join users u on u.id = m.user_id where m.chat_id = chats.id and u.deleted_at is null), chats.legacy_users_count) as "users_count!",
+                array(select t.name from chat_tags ct join tags t on t.id = ct.tag_id where ct.chat_id = chats.id order by t.name) as "tags!""#,
+            chat_id,
+            since,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| map_unique_violation(e, Conflict::ChatNameTaken))?
+        .ok_or(AppError::ChatNotFound)?;
+
+        Ok(chat.try_into()?)
+    }
+
+    async fn get_member(&self, chat_id: Uuid, user_id: Uuid) -> AppResult<Option<ChatMember>> {
+        let member = sqlx::query_as!(MemberRow, "select chat_id, user_id, role from chat_members where chat_id = $1 and user_id = $2", chat_id, user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(member.map(ChatMember::try_from).transpose()?)
+    }
+
+    async fn add_member(&self, member: ChatMember) -> AppResult<ChatMember> {
+        // The no-op update makes `returning` yield the row that was already there
+        let member = sqlx::query_as!(
+            MemberRow,
+            "insert into chat_members(chat_id, user_id, role) values ($1, $2, $3)
+            on conflict (chat_id, user_id) do update set role = chat_members.role
+                returning chat_id, user_id, role",
+            member.chat_id,
+            member.user_id,
+            member.role.as_str(),
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| if is_foreign_key_violation(&e) { AppError::ChatNotFound } else { e.into() })?;
+
+        Ok(member.try_into()?)
+    }
+
+    async fn set_owner(&self, chat_id: Uuid, user_id: Uuid) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        // The previous owner stays on as an ordinary member
+        sqlx::query!("update chat_members set role = 'member' where chat_id = $1 and role = 'owner' and user_id <> $2", chat_id, user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!(
+            "insert into chat_members(chat_id, user_id, role) values ($1, $2, 'owner')
+            on conflict (chat_id, user_id) do update set role = 'owner'",
+            chat_id,
+            user_id,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| if is_foreign_key_violation(&e) { AppError::UserNotFoundByID } else { e.into() })?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn remove_member(&self, chat_id: Uuid, user_id: Uuid) -> AppResult<()> {
+        let result = sqlx::query!("delete from chat_members where chat_id = $1 and user_id = $2", chat_id, user_id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotChatMember);
+        }
+
+        Ok(())
+    }
+
+    async fn get_memberships(&self, user_id: Uuid) -> AppResult<Vec<MembershipDTO>> {
+        let rows = sqlx::query_as!(
+            MembershipRow,
+            "select m.chat_id, c.name as chat_name, m.role, m.joined_at
+            from chat_members m join chats c on c.id = m.chat_id
+            where m.user_id = $1
+            order by m.joined_at, m.chat_id",
+            user_id,
+        )
+        .fetch_all(&self.pool)
         .await?;
 
+        let memberships = rows
+            .into_iter()
+            .map(|row| {
+                Ok(MembershipDTO {
+                    chat_id: row.chat_id,
+                    chat_name: row.chat_name,
+                    role: row.role.parse::<MemberRole>()?,
+                    joined_at: row.joined_at,
+                })
+            })
+            .collect::<Result<Vec<_>, CorruptRow>>()?;
+
+        Ok(memberships)
+    }
+
+    async fn list_members(&self, query: ListMembersDTO) -> AppResult<Page<MemberDTO>> {
+        const MEMBERS: &str = "from chat_members m join users u on u.id = m.user_id where u.deleted_at is null and m.chat_id = ";
+
+        let mut count = QueryBuilder::<Postgres>::new(format!("select count(*) {MEMBERS}"));
+        count.push_bind(query.chat_id);
+
+        let total: i64 = count.build_query_scalar().fetch_one(&self.pool).await?;
+
+        let mut select = QueryBuilder::<Postgres>::new(format!(
+            "select m.user_id, u.name as display_name, m.role, m.joined_at, m.joined_at::text as sort_key {MEMBERS}"
+        ));
+        select.push_bind(query.chat_id);
+
+        if let Some(cursor) = &query.cursor {
+            select
+                .push(" and (m.joined_at, m.user_id) > (")
+                .push_bind(cursor.key.clone())
+                .push("::timestamptz, ")
+                .push_bind(cursor.id)
+                .push(")");
+        }
+
+        // One extra row tells whether another page follows
+        select
+            .push(" order by m.joined_at, m.user_id limit ")
+            .push_bind(i64::from(query.limit) + 1);
+
+        let mut rows: Vec<MemberPageRow> = select.build_query_as().fetch_all(&self.pool).await?;
+
+        let next_cursor = if rows.len() > query.limit as usize {
+            rows.truncate(query.limit as usize);
+
+            rows.last().map(|row| {
+                Cursor {
+                    sort: ListMembersDTO::SORT.to_string(),
+                    key: row.sort_key.clone(),
+                    id: row.user_id,
+                }
+                .encode()
+            })
+        } else {
+            None
+        };
+
+        let items = rows
+            .into_iter()
+            .map(|row| {
+                Ok(MemberDTO {
+                    user_id: row.user_id,
+                    display_name: row.display_name,
+                    role: row.role.parse::<MemberRole>()?,
+                    joined_at: row.joined_at,
+                })
+            })
+            .collect::<Result<Vec<_>, CorruptRow>>()?;
+
+        Ok(Page { items, total, next_cursor })
+    }
+
+    async fn record_link_click(&self, chat_id: Uuid, day: NaiveDate) -> AppResult<()> {
+        sqlx::query!(
+            "insert into chat_link_clicks (chat_id, day, clicks) values ($1, $2, 1)
+            on conflict (chat_id, day) do update set clicks = chat_link_clicks.clicks + 1",
+            chat_id,
+            day,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| if is_foreign_key_violation(&e) { AppError::ChatNotFound } else { e.into() })?;
+
         Ok(())
     }
-}
\ No newline at end of file
+
+    async fn most_followed(&self, query: LinkReportDTO) -> AppResult<Vec<FollowedChatDTO>> {
+        let rows = sqlx::query_as!(
+            FollowedChatRow,
+            r#"select c.id as chat_id, c.name, sum(k.clicks)::bigint as "clicks!", max(k.day) as "last_followed_on!"
+            from chat_link_clicks k
+            join chats c on c.id = k.chat_id
+            where k.day >= $1 and c.deleted_at is null
+            group by c.id, c.name
+            order by sum(k.clicks) desc, c.name
+            limit $2"#,
+            query.since,
+            i64::from(query.limit),
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(FollowedChatDTO::from).collect())
+    }
+
+    async fn purge_chats(&self, before: DateTime<Utc>) -> AppResult<u64> {
+        let result = sqlx::query!("delete from chats where deleted_at <= $1", before)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,22 @@
+use crate::{application::{AppError, errors::Conflict}, domain::entities::errors::DomainError};
+
+/// Turns a unique-constraint violation into the matching conflict, leaving
+/// every other database error as is.
+pub fn map_unique_violation(err: sqlx::Error, conflict: Conflict) -> AppError {
+    match err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => AppError::Conflict(conflict),
+        err => AppError::Database(err),
+    }
+}
+
+/// A referenced row (e.g. a parent location) does not exist, or a row being
+/// deleted is still referenced.
+pub fn is_foreign_key_violation(err: &sqlx::Error) -> bool {
+    matches!(err, sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation())
+}
+
+/// A stored row no longer passes the domain rules, e.g. one written before
+/// they were tightened. The caller sent nothing wrong, so it is a server error.
+#[derive(Debug, thiserror::Error)]
+#[error("stored row could not be decoded: {0}")]
+pub struct CorruptRow(#[from] pub DomainError);
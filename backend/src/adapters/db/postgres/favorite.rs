@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    adapters::db::postgres::{PostgresFavoriteRepo, errors::is_foreign_key_violation},
+    application::{AppError, AppResult, repositories::favorite::FavoriteRepo},
+};
+
+#[async_trait]
+impl FavoriteRepo for PostgresFavoriteRepo {
+    async fn add_favorite(&self, user_id: Uuid, chat_id: Uuid) -> AppResult<()> {
+        sqlx::query!("insert into favorite_chats (user_id, chat_id) values ($1, $2) on conflict do nothing", user_id, chat_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| if is_foreign_key_violation(&e) { AppError::ChatNotFound } else { e.into() })?;
+
+        Ok(())
+    }
+
+    async fn remove_favorite(&self, user_id: Uuid, chat_id: Uuid) -> AppResult<()> {
+        sqlx::query!("delete from favorite_chats where user_id = $1 and chat_id = $2", user_id, chat_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_favorites(&self, user_id: Uuid) -> AppResult<Vec<Uuid>> {
+        let chat_ids = sqlx::query_scalar!(
+            "select chat_id from favorite_chats where user_id = $1 order by created_at desc, chat_id",
+            user_id,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(chat_ids)
+    }
+}
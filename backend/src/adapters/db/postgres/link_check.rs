@@ -0,0 +1,192 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{
+    adapters::db::postgres::{PostgresLinkCheckRepo, errors::{CorruptRow, is_foreign_key_violation}},
+    application::{AppError, AppResult, dto::link_check::{ChatLinkDTO, DeadLinkDTO}, repositories::link_check::LinkCheckRepo},
+    domain::entities::{link_check::{LinkCheck, LinkHealth, ProbeKind, ProbeOutcome}, value_objects::InviteLink},
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct LinkCheckDB {
+    chat_id: Uuid,
+    link: String,
+    outcome: String,
+    status_code: Option<i32>,
+    error: Option<String>,
+    checked_at: DateTime<Utc>,
+}
+
+impl TryFrom<LinkCheckDB> for LinkCheck {
+    type Error = CorruptRow;
+
+    fn try_from(value: LinkCheckDB) -> Result<Self, Self::Error> {
+        let status = value.status_code.and_then(|s| u16::try_from(s).ok()).unwrap_or_default();
+
+        let outcome = match value.outcome.parse()? {
+            ProbeKind::Ok => ProbeOutcome::Ok { status },
+            ProbeKind::Broken => ProbeOutcome::Broken { status },
+            ProbeKind::Unreachable => ProbeOutcome::Unreachable { error: value.error.unwrap_or_default() },
+        };
+
+        Ok(Self {
+            chat_id: value.chat_id,
+            link: InviteLink::parse(&value.link)?,
+            outcome,
+            checked_at: value.checked_at,
+        })
+    }
+}
+
+fn health(link: &str, failures: i32) -> Result<LinkHealth, CorruptRow> {
+    Ok(LinkHealth {
+        link: InviteLink::parse(link)?,
+        failures: u32::try_from(failures).unwrap_or_default(),
+    })
+}
+
+#[derive(sqlx::FromRow)]
+struct ChatLinkRow {
+    chat_id: Uuid,
+    link: String,
+    health_link: Option<String>,
+    failures: Option<i32>,
+}
+
+#[async_trait]
+impl LinkCheckRepo for PostgresLinkCheckRepo {
+    async fn get_chat_links(&self) -> AppResult<Vec<ChatLinkDTO>> {
+        let rows = sqlx::query_as!(
+            ChatLinkRow,
+            r#"select c.id as chat_id, c.invite_link as "link!", h.link as "health_link?", h.failures as "failures?"
+            from chats c
+            left join chat_link_health h on h.chat_id = c.id
+            where c.deleted_at is null and c.invite_link is not null
+            order by h.checked_at nulls first, c.id"#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let links = rows
+            .into_iter()
+            .map(|row| {
+                Ok(ChatLinkDTO {
+                    chat_id: row.chat_id,
+                    link: InviteLink::parse(&row.link)?,
+                    health: match (row.health_link, row.failures) {
+                        (Some(link), Some(failures)) => Some(health(&link, failures)?),
+                        _ => None,
+                    },
+                })
+            })
+            .collect::<Result<_, CorruptRow>>()?;
+
+        Ok(links)
+    }
+
+    async fn add_check(&self, check: &LinkCheck, health: &LinkHealth) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            "insert into chat_link_checks (id, chat_id, link, outcome, status_code, error, checked_at)
+            values ($1, $2, $3, $4, $5, $6, $7)",
+            Uuid::new_v4(),
+            check.chat_id,
+            check.link.as_str(),
+            check.outcome.kind().as_str(),
+            check.outcome.status().map(i32::from),
+            check.outcome.error(),
+            check.checked_at,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| if is_foreign_key_violation(&e) { AppError::ChatNotFound } else { e.into() })?;
+
+        sqlx::query!(
+            "insert into chat_link_health (chat_id, link, failures, checked_at) values ($1, $2, $3, $4)
+            on conflict (chat_id) do update
+            set link = excluded.link, failures = excluded.failures, checked_at = excluded.checked_at",
+            check.chat_id,
+            health.link.as_str(),
+            i32::try_from(health.failures).unwrap_or(i32::MAX),
+            check.checked_at,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn get_checks(&self, chat_id: Uuid, limit: u32) -> AppResult<Vec<LinkCheck>> {
+        let rows = sqlx::query_as!(
+            LinkCheckDB,
+            "select chat_id, link, outcome, status_code, error, checked_at
+            from chat_link_checks
+            where chat_id = $1
+            order by checked_at desc
+            limit $2",
+            chat_id,
+            i64::from(limit),
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let checks = rows
+            .into_iter()
+            .map(LinkCheck::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(checks)
+    }
+
+    async fn get_dead_links(&self, failure_threshold: u32) -> AppResult<Vec<DeadLinkDTO>> {
+        let rows = sqlx::query!(
+            r#"select c.name, h.failures, k.chat_id as "chat_id!", k.link as "link!", k.outcome as "outcome!",
+                k.status_code, k.error, k.checked_at as "checked_at!"
+            from chat_link_health h
+            join chats c on c.id = h.chat_id
+            join lateral (
+                select * from chat_link_checks k where k.chat_id = h.chat_id order by k.checked_at desc limit 1
+            ) k on true
+            where c.deleted_at is null and c.invite_link = h.link and h.failures >= $1
+            order by h.failures desc, c.name"#,
+            i32::try_from(failure_threshold.max(1)).unwrap_or(i32::MAX),
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let dead = rows
+            .into_iter()
+            .map(|row| {
+                let last_check = LinkCheckDB {
+                    chat_id: row.chat_id,
+                    link: row.link,
+                    outcome: row.outcome,
+                    status_code: row.status_code,
+                    error: row.error,
+                    checked_at: row.checked_at,
+                };
+
+                Ok(DeadLinkDTO {
+                    chat_id: last_check.chat_id,
+                    name: row.name,
+                    health: health(&last_check.link, row.failures)?,
+                    last_check: last_check.try_into()?,
+                })
+            })
+            .collect::<Result<_, CorruptRow>>()?;
+
+        Ok(dead)
+    }
+
+    async fn purge_checks(&self, before: DateTime<Utc>) -> AppResult<u64> {
+        let result = sqlx::query!("delete from chat_link_checks where checked_at < $1", before)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,216 @@
+use async_trait::async_trait;
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use crate::{
+    adapters::db::postgres::{PostgresLocationRepo, errors::{CorruptRow, is_foreign_key_violation, map_unique_violation}},
+    application::{AppError, AppResult, errors::Conflict, repositories::location::LocationRepo},
+    domain::entities::{boundary::Boundary, location::{District, LocationNode}, value_objects::{GeoPoint, PlaceName}},
+};
+
+/// Polygons of rings of `[lon, lat]`, the GeoJSON MultiPolygon layout.
+type BoundaryJson = Vec<Vec<Vec<[f64; 2]>>>;
+
+#[derive(Debug, sqlx::FromRow)]
+struct LocationDB {
+    id: Uuid,
+    parent_id: Option<Uuid>,
+    kind: String,
+    name: String,
+}
+
+impl TryFrom<LocationDB> for LocationNode {
+    type Error = CorruptRow;
+
+    fn try_from(value: LocationDB) -> Result<Self, Self::Error> {
+        Ok(LocationNode::new(
+            Some(value.id),
+            value.parent_id,
+            value.kind.parse()?,
+            PlaceName::parse(&value.name)?,
+        ))
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DistrictDB {
+    id: Uuid,
+    parent_id: Option<Uuid>,
+    kind: String,
+    name: String,
+    boundary: Json<BoundaryJson>,
+}
+
+impl TryFrom<DistrictDB> for District {
+    type Error = CorruptRow;
+
+    fn try_from(value: DistrictDB) -> Result<Self, Self::Error> {
+        let polygons = value
+            .boundary
+            .0
+            .into_iter()
+            .map(|rings| {
+                rings
+                    .into_iter()
+                    .map(|ring| ring.into_iter().map(|[lon, lat]| GeoPoint::new(lat, lon)).collect())
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        let location = LocationDB { id: value.id, parent_id: value.parent_id, kind: value.kind, name: value.name };
+
+        Ok(District::new(location.try_into()?, Boundary::new(polygons)?)?)
+    }
+}
+
+fn boundary_json(boundary: &Boundary) -> BoundaryJson {
+    boundary
+        .polygons()
+        .iter()
+        .map(|rings| rings.iter().map(|ring| ring.iter().map(|p| [p.lon(), p.lat()]).collect()).collect())
+        .collect()
+}
+
+fn districts(rows: Vec<DistrictDB>) -> AppResult<Vec<District>> {
+    Ok(rows.into_iter().map(District::try_from).collect::<Result<Vec<_>, _>>()?)
+}
+
+#[async_trait]
+impl LocationRepo for PostgresLocationRepo {
+    async fn create_location(&self, location: LocationNode) -> AppResult<LocationNode> {
+        let location = sqlx::query_as!(
+            LocationDB,
+            "insert into locations(id, parent_id, kind, name)
+            values ($1, $2, $3, $4)
+                returning id, parent_id, kind, name",
+            location.id,
+            location.parent_id,
+            location.kind.as_str(),
+            location.name.as_str(),
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| map_unique_violation(e, Conflict::LocationNameTaken))?;
+
+        Ok(location.try_into()?)
+    }
+
+    async fn get_location(&self, id: Uuid) -> AppResult<LocationNode> {
+        let location = sqlx::query_as!(LocationDB, "select id, parent_id, kind, name from locations where id = $1", id)
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or(AppError::LocationNotFound)?;
+
+        Ok(location.try_into()?)
+    }
+
+    async fn get_locations(&self) -> AppResult<Vec<LocationNode>> {
+        let locations = sqlx::query_as!(LocationDB, "select id, parent_id, kind, name from locations order by lower(name)")
+        .fetch_all(&self.pool)
+        .await?;
+
+        let locations = locations
+            .into_iter()
+            .map(LocationNode::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(locations)
+    }
+
+    async fn update_location(&self, location: LocationNode) -> AppResult<LocationNode> {
+        let location = sqlx::query_as!(
+            LocationDB,
+            "update locations set parent_id = $2, name = $3
+            where id = $1
+                returning id, parent_id, kind, name",
+            location.id,
+            location.parent_id,
+            location.name.as_str(),
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| map_unique_violation(e, Conflict::LocationNameTaken))?
+        .ok_or(AppError::LocationNotFound)?;
+
+        Ok(location.try_into()?)
+    }
+
+    async fn delete_location(&self, id: Uuid) -> AppResult<()> {
+        // Deleted chats only lose the link (`on delete set null`), live ones block
+        let live_chats = sqlx::query_scalar!(r#"select count(*) as "count!" from chats where location_id = $1 and deleted_at is null"#, id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        if live_chats > 0 {
+            return Err(AppError::Conflict(Conflict::LocationInUse));
+        }
+
+        let result = sqlx::query!("delete from locations where id = $1", id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| if is_foreign_key_violation(&e) { AppError::Conflict(Conflict::LocationInUse) } else { e.into() })?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::LocationNotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn set_boundary(&self, id: Uuid, boundary: Option<&Boundary>) -> AppResult<()> {
+        let (min_lat, max_lat, min_lon, max_lon) = match boundary.map(Boundary::bounding_box) {
+            Some((min_lat, max_lat, min_lon, max_lon)) => (Some(min_lat), Some(max_lat), Some(min_lon), Some(max_lon)),
+            None => (None, None, None, None),
+        };
+
+        let result = sqlx::query!(
+            "update locations
+            set boundary = $2, min_lat = $3, max_lat = $4, min_lon = $5, max_lon = $6
+            where id = $1",
+            id,
+            boundary.map(|b| Json(boundary_json(b))) as _,
+            min_lat,
+            max_lat,
+            min_lon,
+            max_lon,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::LocationNotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn get_districts(&self) -> AppResult<Vec<District>> {
+        let rows = sqlx::query_as!(
+            DistrictDB,
+            r#"select id, parent_id, kind, name, boundary as "boundary!: Json<BoundaryJson>"
+            from locations where boundary is not null order by lower(name)"#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        districts(rows)
+    }
+
+    async fn find_districts_around(&self, point: GeoPoint) -> AppResult<Vec<District>> {
+        let rows = sqlx::query_as!(
+            DistrictDB,
+            r#"select id, parent_id, kind, name, boundary as "boundary!: Json<BoundaryJson>"
+            from locations
+            where boundary is not null
+                and $1 between min_lat and max_lat
+                and $2 between min_lon and max_lon
+            order by lower(name)"#,
+            point.lat(),
+            point.lon(),
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        districts(rows)
+    }
+}
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::{
+    adapters::db::postgres::{PostgresNotificationRepo, errors::CorruptRow},
+    application::{AppError, AppResult, repositories::notification::NotificationRepo},
+    domain::entities::notification::Notification,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct NotificationDB {
+    id: Uuid,
+    user_id: Uuid,
+    kind: String,
+    submission_id: Option<Uuid>,
+    note: Option<String>,
+    created_at: DateTime<Utc>,
+    read_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<NotificationDB> for Notification {
+    type Error = CorruptRow;
+
+    fn try_from(value: NotificationDB) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id,
+            user_id: value.user_id,
+            kind: value.kind.parse()?,
+            submission_id: value.submission_id,
+            note: value.note,
+            created_at: value.created_at,
+            read_at: value.read_at,
+        })
+    }
+}
+
+/// Notifications are written as part of whatever caused them.
+pub(super) async fn insert_notification(conn: &mut PgConnection, notification: &Notification) -> AppResult<()> {
+    sqlx::query!(
+        "insert into notifications(id, user_id, kind, submission_id, note, created_at)
+        values ($1, $2, $3, $4, $5, $6)",
+        notification.id,
+        notification.user_id,
+        notification.kind.as_str(),
+        notification.submission_id,
+        notification.note.as_deref(),
+        notification.created_at,
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl NotificationRepo for PostgresNotificationRepo {
+    async fn get_notifications(&self, user_id: Uuid) -> AppResult<Vec<Notification>> {
+        let notifications = sqlx::query_as!(
+            NotificationDB,
+            "select id, user_id, kind, submission_id, note, created_at, read_at
+            from notifications where user_id = $1 order by created_at desc",
+            user_id,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let notifications = notifications
+            .into_iter()
+            .map(Notification::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(notifications)
+    }
+
+    async fn mark_read(&self, user_id: Uuid, id: Uuid) -> AppResult<()> {
+        let result = sqlx::query!("update notifications set read_at = coalesce(read_at, now()) where id = $1 and user_id = $2", id, user_id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotificationNotFound);
+        }
+
+        Ok(())
+    }
+}
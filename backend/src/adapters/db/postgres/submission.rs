@@ -0,0 +1,193 @@
+use async_trait::async_trait;
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::{
+    adapters::db::postgres::{PostgresSubmissionRepo, chat::insert_chat, errors::{CorruptRow, is_foreign_key_violation}, notification::insert_notification},
+    application::{AppError, AppResult, dto::{chat::CreateChatDTO, submission::SubmissionFilterDTO}, repositories::submission::SubmissionRepo},
+    domain::entities::{
+        errors::DomainError,
+        notification::Notification,
+        submission::{ChatProposal, ChatSubmission, SubmissionStatus},
+        value_objects::{ChatName, GeoPoint, Location},
+    },
+};
+
+/// Columns of [`SubmissionDB`], for queries assembled at runtime.
+const SUBMISSION_COLUMNS: &str = "id, submitter_id, name, location, description, latitude, longitude, location_id, category, status, review_note, chat_id";
+
+#[derive(Debug, sqlx::FromRow)]
+struct SubmissionDB {
+    id: Uuid,
+    submitter_id: Uuid,
+    name: String,
+    location: String,
+    description: String,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    location_id: Option<Uuid>,
+    category: String,
+    status: String,
+    review_note: Option<String>,
+    chat_id: Option<Uuid>,
+}
+
+impl TryFrom<SubmissionDB> for ChatSubmission {
+    type Error = CorruptRow;
+
+    fn try_from(value: SubmissionDB) -> Result<Self, Self::Error> {
+        let proposal = ChatProposal {
+            name: ChatName::parse(&value.name)?,
+            description: value.description,
+            location: Location::parse(&value.location)?,
+            position: match (value.latitude, value.longitude) {
+                (Some(lat), Some(lon)) => Some(GeoPoint::new(lat, lon)?),
+                _ => None,
+            },
+            location_id: value.location_id,
+            category: value.category.parse()?,
+        };
+
+        Ok(ChatSubmission::new(value.submitter_id, proposal)
+            .with_id(value.id)
+            .with_review(value.status.parse()?, value.review_note, value.chat_id))
+    }
+}
+
+#[async_trait]
+impl SubmissionRepo for PostgresSubmissionRepo {
+    async fn add_submission(&self, submission: ChatSubmission) -> AppResult<ChatSubmission> {
+        let proposal = &submission.proposal;
+
+        let submission = sqlx::query_as!(
+            SubmissionDB,
+            "insert into chat_submissions(id, submitter_id, name, location, description, latitude, longitude, location_id, category, status)
+            values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                returning id, submitter_id, name, location, description, latitude, longitude, location_id, category, status, review_note, chat_id",
+            submission.id,
+            submission.submitter_id,
+            proposal.name.as_str(),
+            proposal.location.as_str(),
+            &proposal.description,
+            proposal.position.map(|p| p.lat()),
+            proposal.position.map(|p| p.lon()),
+            proposal.location_id,
+            proposal.category.as_str(),
+            submission.status.as_str(),
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| if is_foreign_key_violation(&e) { AppError::LocationNotFound } else { e.into() })?;
+
+        Ok(submission.try_into()?)
+    }
+
+    async fn get_submission(&self, id: Uuid) -> AppResult<ChatSubmission> {
+        let submission = sqlx::query_as!(
+            SubmissionDB,
+            "select id, submitter_id, name, location, description, latitude, longitude, location_id, category, status, review_note, chat_id
+            from chat_submissions where id = $1",
+            id,
+        )
+            .fetch_optional(&self.pool)
+            .await?
+            .ok_or(AppError::SubmissionNotFound)?;
+
+        Ok(submission.try_into()?)
+    }
+
+    async fn get_submissions(&self, filter: SubmissionFilterDTO) -> AppResult<Vec<ChatSubmission>> {
+        let mut query = QueryBuilder::<Postgres>::new(format!("select {SUBMISSION_COLUMNS} from chat_submissions where true"));
+
+        if let Some(status) = filter.status {
+            query.push(" and status = ").push_bind(status.as_str());
+        }
+
+        if let Some(submitter_id) = filter.submitter_id {
+            query.push(" and submitter_id = ").push_bind(submitter_id);
+        }
+
+        let submissions: Vec<SubmissionDB> = query
+            .push(" order by created_at, id")
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await?;
+
+        let submissions = submissions
+            .into_iter()
+            .map(ChatSubmission::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(submissions)
+    }
+
+    async fn revise_submission(&self, submission: ChatSubmission) -> AppResult<ChatSubmission> {
+        let proposal = &submission.proposal;
+
+        let submission = sqlx::query_as!(
+            SubmissionDB,
+            "update chat_submissions
+            set name = $2, location = $3, description = $4, latitude = $5, longitude = $6, location_id = $7, category = $8,
+                status = $9, review_note = $10, updated_at = now()
+            where id = $1 and status in ('pending', 'changes_requested')
+                returning id, submitter_id, name, location, description, latitude, longitude, location_id, category, status, review_note, chat_id",
+            submission.id,
+            proposal.name.as_str(),
+            proposal.location.as_str(),
+            &proposal.description,
+            proposal.position.map(|p| p.lat()),
+            proposal.position.map(|p| p.lon()),
+            proposal.location_id,
+            proposal.category.as_str(),
+            submission.status.as_str(),
+            submission.review_note.as_deref(),
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| if is_foreign_key_violation(&e) { AppError::LocationNotFound } else { e.into() })?
+        .ok_or(DomainError::SubmissionAlreadyDecided)?;
+
+        Ok(submission.try_into()?)
+    }
+
+    async fn decide_submission(&self, submission: ChatSubmission, notification: Notification) -> AppResult<ChatSubmission> {
+        let mut tx = self.pool.begin().await?;
+
+        // Claimed first: a concurrent decision waits on the row lock and then
+        // finds it no longer pending, before anything else is written
+        let mut decided = sqlx::query_as!(
+            SubmissionDB,
+            "update chat_submissions set status = $2, review_note = $3, updated_at = now()
+            where id = $1 and status = 'pending'
+                returning id, submitter_id, name, location, description, latitude, longitude, location_id, category, status, review_note, chat_id",
+            submission.id,
+            submission.status.as_str(),
+            submission.review_note.as_deref(),
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(DomainError::SubmissionAlreadyDecided)?;
+
+        if submission.status == SubmissionStatus::Approved
+            && let Some(chat_id) = submission.chat_id
+        {
+            insert_chat(&mut tx, chat_id, CreateChatDTO::from(submission.proposal.clone())).await?;
+
+            sqlx::query!("insert into chat_members(chat_id, user_id, role) values ($1, $2, 'owner')", chat_id, submission.submitter_id)
+                .execute(&mut *tx)
+                .await?;
+
+            sqlx::query!("update chat_submissions set chat_id = $2 where id = $1", submission.id, chat_id)
+                .execute(&mut *tx)
+                .await?;
+
+            decided.chat_id = Some(chat_id);
+        }
+
+        insert_notification(&mut tx, &notification).await?;
+
+        tx.commit().await?;
+
+        Ok(decided.try_into()?)
+    }
+}
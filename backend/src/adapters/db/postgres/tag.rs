@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    adapters::db::postgres::{PostgresTagRepo, errors::{CorruptRow, map_unique_violation}},
+    application::{AppError, AppResult, errors::Conflict, repositories::tag::TagRepo},
+    domain::entities::{tag::Tag, value_objects::TagName},
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct TagDB {
+    id: Uuid,
+    name: String,
+}
+
+impl TryFrom<TagDB> for Tag {
+    type Error = CorruptRow;
+
+    fn try_from(value: TagDB) -> Result<Self, Self::Error> {
+        Ok(Tag::new(Some(value.id), TagName::parse(&value.name)?))
+    }
+}
+
+#[async_trait]
+impl TagRepo for PostgresTagRepo {
+    async fn create_tag(&self, tag: Tag) -> AppResult<Tag> {
+        let tag = sqlx::query_as!(TagDB, "insert into tags(id, name) values ($1, $2) returning id, name", tag.id, tag.name.as_str())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| map_unique_violation(e, Conflict::TagNameTaken))?;
+
+        Ok(tag.try_into()?)
+    }
+
+    async fn get_tags(&self) -> AppResult<Vec<Tag>> {
+        let tags = sqlx::query_as!(TagDB, "select id, name from tags order by name")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let tags = tags
+            .into_iter()
+            .map(Tag::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(tags)
+    }
+
+    async fn rename_tag(&self, tag: Tag) -> AppResult<Tag> {
+        let tag = sqlx::query_as!(TagDB, "update tags set name = $2 where id = $1 returning id, name", tag.id, tag.name.as_str())
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| map_unique_violation(e, Conflict::TagNameTaken))?
+            .ok_or(AppError::TagNotFound)?;
+
+        Ok(tag.try_into()?)
+    }
+
+    async fn delete_tag(&self, id: Uuid) -> AppResult<()> {
+        let result = sqlx::query!("delete from tags where id = $1", id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::TagNotFound);
+        }
+
+        Ok(())
+    }
+}
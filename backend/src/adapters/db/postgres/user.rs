@@ -1,30 +1,74 @@
 use std::str::FromStr;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, QueryBuilder};
 use uuid::Uuid;
 
 use crate::{
-    application::{AppResult, dto::user::{CreateNewUserDTO, DeleteUserDTO}, repositories::user::UserRepository},
-    domain::entities::user::User,
+    adapters::db::postgres::errors::{CorruptRow, map_unique_violation},
+    application::{AppError, AppResult, dto::user::{CreateNewUserDTO, DeleteUserDTO, ListUsersDTO, UpdatePrivacyDTO, UserExportDTO, UserSort}, errors::Conflict, pagination::{Cursor, Page, SortOrder}, retention::Deleted, repositories::user::UserRepository},
+    domain::entities::{submission::SubmissionStatus, user::{PrivacySettings, User, UserRole, UserStatus}, value_objects::{DisplayName, Email}},
 };
 
 use super::PostgresUserRepo;
 
+/// Columns of [`UserDB`], for queries assembled at runtime.
+const USER_COLUMNS: &str = "id, name, email, role, status, show_email, show_role, password_hash, token_salt";
+
 #[derive(sqlx::FromRow, Debug)]
 struct UserDB {
     id: uuid::Uuid,
     name: String,
     email: String,
     role: String,
+    status: String,
+    show_email: bool,
+    show_role: bool,
     password_hash: String,
     token_salt: Option<String>,
 }
 
-impl From<UserDB> for User {
-    fn from(value: UserDB) -> Self {
+/// Lifts a `query!` record carrying the user columns into a [`UserDB`].
+macro_rules! user_db {
+    ($row:expr) => {
+        UserDB {
+            id: $row.id,
+            name: $row.name,
+            email: $row.email,
+            role: $row.role,
+            status: $row.status,
+            show_email: $row.show_email,
+            show_role: $row.show_role,
+            password_hash: $row.password_hash,
+            token_salt: $row.token_salt,
+        }
+    };
+}
+
+#[derive(sqlx::FromRow)]
+struct UserPageRow {
+    #[sqlx(flatten)]
+    user: UserDB,
+    sort_key: String,
+}
+
+impl TryFrom<UserDB> for User {
+    type Error = CorruptRow;
+
+    fn try_from(value: UserDB) -> Result<Self, Self::Error> {
         let token_hash = value.token_salt.unwrap_or(String::from("token")); // FIXME Handle cases when no token in db
 
-        User::new(value.id, value.name, value.email, value.role, value.password_hash, token_hash)
+        Ok(User::new(
+            value.id,
+            DisplayName::from_stored(value.name),
+            Email::from_stored(value.email),
+            value.role.parse::<UserRole>()?,
+            value.status.parse::<UserStatus>()?,
+            value.password_hash,
+            token_hash,
+        )
+        .with_privacy(PrivacySettings { show_email: value.show_email, show_role: value.show_role }))
     }
 }
 
@@ -35,20 +79,21 @@ impl UserRepository for PostgresUserRepo {
 
         let user = sqlx::query_as!(
             UserDB,
-            r#"insert into users(id,name,email,role,password_hash,token_salt)
+            "insert into users(id, name, email, role, password_hash, token_salt)
             values ($1, $2, $3, $4, $5, $6)
-                returning id,name,email,role,password_hash,token_salt"#,
+                returning id, name, email, role, status, show_email, show_role, password_hash, token_salt",
             Uuid::new_v4(),
-            user_dto.name,
-            user_dto.email,
-            "user".to_string(),
+            user_dto.name.as_str(),
+            user_dto.email.as_str(),
+            UserRole::User.as_str(),
             user_dto.password,
             token,
         )
         .fetch_one(&self.pool)
-        .await?;
+        .await
+        .map_err(|e| map_unique_violation(e, Conflict::EmailTaken))?;
 
-        Ok(user.into())
+        Ok(user.try_into()?)
     }
 
     async fn get_user_by_id(
@@ -59,15 +104,14 @@ impl UserRepository for PostgresUserRepo {
 
         let user = sqlx::query_as!(
             UserDB,
-            r#"select *
-            from users
-            where id = $1"#,
+            "select id, name, email, role, status, show_email, show_role, password_hash, token_salt
+            from users where id = $1 and deleted_at is null",
             user_id,
         )
         .fetch_one(&self.pool)
         .await?;
 
-        Ok(user.into())
+        Ok(user.try_into()?)
     }
 
     async fn get_user_by_email(
@@ -76,43 +120,267 @@ impl UserRepository for PostgresUserRepo {
     ) -> AppResult<User> {
         let user = sqlx::query_as!(
             UserDB,
-            r#"select *
-            from users
-            where email = $1"#,
+            "select id, name, email, role, status, show_email, show_role, password_hash, token_salt
+            from users where email = $1 and deleted_at is null",
             user_dto.email,
         )
         .fetch_one(&self.pool)
         .await?;
 
-        Ok(user.into())
+        Ok(user.try_into()?)
     }
 
     async fn get_users(&self) -> AppResult<Vec<User>> {
         let users = sqlx::query_as!(
             UserDB,
-            r#"select *
-            from users
-            "#
+            "select id, name, email, role, status, show_email, show_role, password_hash, token_salt
+            from users where deleted_at is null"
         )
         .fetch_all(&self.pool)
         .await?;
 
-    let users_results = users.into_iter().map(|u| u.into()).collect();
+    let users_results = users
+        .into_iter()
+        .map(User::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok(users_results)
     }
 
-    async fn delete_user_by_id(&self, user_dto: DeleteUserDTO) -> AppResult<()> {
-        let query = "DELETE FROM users WHERE id = $1";
+    async fn list_users(&self, query: ListUsersDTO) -> AppResult<Page<User>> {
+        let mut count = QueryBuilder::<Postgres>::new("select count(*) from users where deleted_at is null");
+        push_filters(&mut count, &query);
+
+        let total: i64 = count.build_query_scalar().fetch_one(&self.pool).await?;
+
+        let key = sort_key(query.sort);
+        let direction = query.order.as_sql();
+
+        let mut select = QueryBuilder::<Postgres>::new(format!("select {USER_COLUMNS}, {key}::text as sort_key from users where deleted_at is null"));
+        push_filters(&mut select, &query);
+
+        if let Some(cursor) = &query.cursor {
+            let comparison = match query.order {
+                SortOrder::Asc => ">",
+                SortOrder::Desc => "<",
+            };
+
+            let cast = match query.sort {
+                UserSort::CreatedAt => "timestamptz",
+                UserSort::Name | UserSort::Email => "text",
+            };
+
+            select
+                .push(format!(" and ({key}, id) {comparison} ("))
+                .push_bind(cursor.key.clone())
+                .push(format!("::{cast}, "))
+                .push_bind(cursor.id)
+                .push(")");
+        }
+
+        // One extra row tells whether another page follows
+        select
+            .push(format!(" order by {key} {direction}, id {direction} limit "))
+            .push_bind(i64::from(query.limit) + 1);
+
+        let mut rows: Vec<UserPageRow> = select.build_query_as().fetch_all(&self.pool).await?;
+
+        let next_cursor = if rows.len() > query.limit as usize {
+            rows.truncate(query.limit as usize);
+
+            rows.last().map(|row| {
+                Cursor {
+                    sort: query.sort.as_str().to_string(),
+                    key: row.sort_key.clone(),
+                    id: row.user.id,
+                }
+                .encode()
+            })
+        } else {
+            None
+        };
+
+        let items = rows
+            .into_iter()
+            .map(|row| User::try_from(row.user))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Page { items, total, next_cursor })
+    }
 
+    async fn update_privacy(&self, user_dto: UpdatePrivacyDTO) -> AppResult<User> {
         let user_id = Uuid::from_str(&user_dto.id)?;
 
-        sqlx::query(
-            query
-        ).bind(user_id)
+        // Omitted settings keep their current value
+        let user = sqlx::query_as!(
+            UserDB,
+            "update users
+            set show_email = coalesce($2, show_email), show_role = coalesce($3, show_role)
+            where id = $1 and deleted_at is null
+                returning id, name, email, role, status, show_email, show_role, password_hash, token_salt",
+            user_id,
+            user_dto.show_email,
+            user_dto.show_role,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(user.try_into()?)
+    }
+
+    async fn delete_user_by_id(&self, user_dto: DeleteUserDTO) -> AppResult<()> {
+        let user_id = Uuid::from_str(&user_dto.id)?;
+
+        sqlx::query!("update users set deleted_at = now() where id = $1 and deleted_at is null", user_id)
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
+
+    async fn anonymise_user(&self, id: &str) -> AppResult<()> {
+        let user_id = Uuid::from_str(id)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        // The placeholder address keeps the unique email index satisfied
+        sqlx::query!(
+            "update users
+            set name = 'Deleted user', email = 'closed-' || id || '@deleted.invalid',
+                password_hash = '', token_salt = null, status = $2,
+                show_email = false, show_role = false
+            where id = $1",
+            user_id,
+            UserStatus::Closed.as_str(),
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // A closed account no longer counts towards any chat
+        sqlx::query!("delete from chat_members where user_id = $1", user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!("delete from notifications where user_id = $1", user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        // Proposals nobody has published yet go with the account
+        sqlx::query!(
+            "delete from chat_submissions where submitter_id = $1 and status <> $2",
+            user_id,
+            SubmissionStatus::Approved.as_str(),
+        )
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query!("delete from favorite_chats where user_id = $1", user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn export_user(&self, id: &str) -> AppResult<UserExportDTO> {
+        let user_id = Uuid::from_str(id)?;
+
+        let row = sqlx::query!(
+            "select id, name, email, role, status, show_email, show_role, password_hash, token_salt, created_at
+            from users where id = $1 and deleted_at is null",
+            user_id,
+        )
+            .fetch_one(&self.pool)
+            .await?;
+
+        let user = User::try_from(user_db!(row))?;
+
+        Ok(UserExportDTO::new(user.into(), row.created_at))
+    }
+
+    async fn get_deleted_users(&self, since: DateTime<Utc>) -> AppResult<Vec<Deleted<User>>> {
+        let rows = sqlx::query!(
+            r#"select id, name, email, role, status, show_email, show_role, password_hash, token_salt, deleted_at as "deleted_at!"
+            from users where deleted_at > $1 order by deleted_at desc"#,
+            since,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let users = rows
+            .into_iter()
+            .map(|row| Ok(Deleted { deleted_at: row.deleted_at, item: User::try_from(user_db!(row))? }))
+            .collect::<Result<Vec<_>, CorruptRow>>()?;
+
+        Ok(users)
+    }
+
+    async fn restore_user(&self, id: &str, since: DateTime<Utc>) -> AppResult<User> {
+        let user_id = Uuid::from_str(id)?;
+
+        let user = sqlx::query_as!(
+            UserDB,
+            "update users set deleted_at = null
+            where id = $1 and deleted_at > $2
+                returning id, name, email, role, status, show_email, show_role, password_hash, token_salt",
+            user_id,
+            since,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| map_unique_violation(e, Conflict::EmailTaken))?
+        .ok_or(AppError::UserNotFoundByID)?;
+
+        Ok(user.try_into()?)
+    }
+
+    async fn purge_users(&self, before: DateTime<Utc>) -> AppResult<u64> {
+        let result = sqlx::query!("delete from users where deleted_at <= $1", before)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+fn sort_key(sort: UserSort) -> &'static str {
+    match sort {
+        UserSort::Name => "lower(name)",
+        UserSort::Email => "email",
+        UserSort::CreatedAt => "created_at",
+    }
+}
+
+fn push_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &ListUsersDTO) {
+    if let Some(search) = &query.search {
+        let pattern = format!("{}%", escape_like(search));
+
+        builder.push(" and (lower(name) like ").push_bind(pattern.clone());
+
+        if !query.public {
+            builder.push(" or email like ").push_bind(pattern);
+        }
+
+        builder.push(")");
+    }
+
+    if let Some(role) = query.role {
+        builder.push(" and role = ").push_bind(role.as_str());
+
+        if query.public {
+            builder.push(" and show_role");
+        }
+    }
+
+    if let Some(status) = query.status {
+        builder.push(" and status = ").push_bind(status.as_str());
+    }
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
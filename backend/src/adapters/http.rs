@@ -0,0 +1 @@
+pub mod prober;
\ No newline at end of file
@@ -0,0 +1,101 @@
+use std::{error::Error, time::Duration};
+
+use async_trait::async_trait;
+use reqwest::{Client, redirect::Policy};
+
+use crate::{application::repositories::link_prober::LinkProber, domain::entities::{link_check::ProbeOutcome, value_objects::{InviteLink, LinkAllowList}}};
+
+const USER_AGENT: &str = concat!("ncity-chat-network-link-checker/", env!("CARGO_PKG_VERSION"));
+
+const MAX_REDIRECTS: usize = 5;
+
+/// Asks the messenger over HTTPS. Redirects are followed only while they stay
+/// on `https` and on allowed hosts; where one leaves them, its own status is
+/// the answer. Links to hosts no longer allowed are not requested at all.
+pub struct HttpLinkProber {
+    client: Client,
+    allow_list: LinkAllowList,
+}
+
+impl HttpLinkProber {
+    pub fn new(timeout: Duration, allow_list: LinkAllowList) -> reqwest::Result<Self> {
+        let redirect_allow_list = allow_list.clone();
+
+        let redirects = Policy::custom(move |attempt| {
+            let allowed = attempt.url().scheme() == "https"
+                && attempt.url().host_str().is_some_and(|host| redirect_allow_list.allows_host(host));
+
+            if attempt.previous().len() > MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if !allowed {
+                attempt.stop()
+            } else {
+                attempt.follow()
+            }
+        });
+
+        let client = Client::builder()
+            .timeout(timeout)
+            .redirect(redirects)
+            .user_agent(USER_AGENT)
+            .build()?;
+
+        Ok(Self { client, allow_list })
+    }
+}
+
+#[async_trait]
+impl LinkProber for HttpLinkProber {
+    async fn probe(&self, link: &InviteLink) -> ProbeOutcome {
+        if let Err(e) = self.allow_list.check(link) {
+            return ProbeOutcome::Unreachable { error: e.to_string() };
+        }
+
+        match self.client.get(link.as_str()).send().await {
+            Ok(res) => ProbeOutcome::from_status(res.status().as_u16()),
+            Err(e) => ProbeOutcome::Unreachable { error: describe(e.without_url()) },
+        }
+    }
+}
+
+/// reqwest keeps the interesting part, e.g. a DNS or TLS failure, in the source chain.
+fn describe(e: reqwest::Error) -> String {
+    let mut message = e.to_string();
+    let mut source = e.source();
+
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+
+    message
+}
+
+/// Answers from a table instead of the network; links missing from it are
+/// unreachable.
+#[cfg(test)]
+#[derive(Default)]
+pub struct StubLinkProber {
+    outcomes: std::sync::Mutex<std::collections::HashMap<String, ProbeOutcome>>,
+}
+
+#[cfg(test)]
+impl StubLinkProber {
+    pub fn set(&self, link: &str, outcome: ProbeOutcome) {
+        self.outcomes.lock().unwrap().insert(link.to_string(), outcome);
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl LinkProber for StubLinkProber {
+    async fn probe(&self, link: &InviteLink) -> ProbeOutcome {
+        self.outcomes
+            .lock()
+            .unwrap()
+            .get(link.as_str())
+            .cloned()
+            .unwrap_or_else(|| ProbeOutcome::Unreachable { error: "no such host".to_string() })
+    }
+}
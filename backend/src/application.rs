@@ -2,6 +2,10 @@ pub mod use_cases;
 pub mod repositories;
 pub mod dto;
 pub mod errors;
+pub mod validation;
+pub mod pagination;
+pub mod retention;
+pub mod link_check;
 
 pub use self::errors::AppError;
 
@@ -1,2 +1,6 @@
 pub mod user;
-pub mod chat;
\ No newline at end of file
+pub mod chat;
+pub mod location;
+pub mod tag;
+pub mod submission;
+pub mod link_check;
\ No newline at end of file
@@ -0,0 +1,223 @@
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::{
+    adapters::api::chat::chat_controller::{ChatsQuery, CreateChatPayload, DEFAULT_LINK_REPORT_DAYS, DEFAULT_LINK_REPORT_LIMIT, DEFAULT_NEARBY_LIMIT, DEFAULT_NEARBY_RADIUS_M, LinkReportQuery, MembersQuery, NearbyChatsQuery, UpdateChatPayload},
+    application::{AppError, pagination::{Cursor, DEFAULT_PAGE_SIZE}},
+    domain::entities::{
+        chat::{Chat, ChatAction, ChatCategory},
+        errors::DomainError,
+        member::MemberRole,
+        value_objects::{ChatName, GeoPoint, InviteLink, Location, TagName},
+    },
+};
+
+/// Parses tag names, dropping repeats.
+fn parse_tags<'a>(values: impl IntoIterator<Item = &'a str>) -> Result<Vec<TagName>, DomainError> {
+    let mut tags: Vec<TagName> = Vec::new();
+
+    for value in values {
+        let tag = TagName::parse(value)?;
+
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    Ok(tags)
+}
+
+pub struct CreateChatDTO {
+    pub name: ChatName,
+    pub description: String,
+    pub location: Location,
+    pub position: Option<GeoPoint>,
+    pub location_id: Option<Uuid>,
+    pub category: ChatCategory,
+    /// Must already be in the vocabulary
+    pub tags: Vec<TagName>,
+    pub invite_link: Option<InviteLink>,
+}
+
+impl TryFrom<CreateChatPayload> for CreateChatDTO {
+    type Error = DomainError;
+
+    fn try_from(value: CreateChatPayload) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: ChatName::parse(&value.name)?,
+            description: value.description.trim().to_string(),
+            location: Location::parse(&value.location)?,
+            position: match (value.latitude, value.longitude) {
+                (Some(lat), Some(lon)) => Some(GeoPoint::new(lat, lon)?),
+                (None, None) => None,
+                _ => return Err(DomainError::CoordinatesInvalid),
+            },
+            location_id: value.location_id,
+            category: value.category.as_deref().map(str::parse).transpose()?.unwrap_or_default(),
+            tags: parse_tags(value.tags.iter().map(String::as_str))?,
+            invite_link: value.invite_link.as_deref().map(InviteLink::parse).transpose()?,
+        })
+    }
+}
+
+/// Edits a chat; `None` leaves a field as it is, tags are replaced as a
+/// whole and `Some(None)` removes the invite link.
+pub struct UpdateChatDTO {
+    pub id: Uuid,
+    pub category: Option<ChatCategory>,
+    pub tags: Option<Vec<TagName>>,
+    pub description: Option<String>,
+    pub invite_link: Option<Option<InviteLink>>,
+}
+
+impl UpdateChatDTO {
+    /// What the edit touches, for `Chat::authorize`.
+    pub fn actions(&self) -> Vec<ChatAction> {
+        let mut actions = Vec::new();
+
+        if self.category.is_some() || self.tags.is_some() {
+            actions.push(ChatAction::Reclassify);
+        }
+
+        if self.description.is_some() || self.invite_link.is_some() {
+            actions.push(ChatAction::EditDetails);
+        }
+
+        actions
+    }
+}
+
+impl UpdateChatDTO {
+    pub fn try_new(id: Uuid, payload: UpdateChatPayload) -> Result<Self, DomainError> {
+        Ok(Self {
+            id,
+            category: payload.category.as_deref().map(str::parse).transpose()?,
+            tags: payload.tags.map(|tags| parse_tags(tags.iter().map(String::as_str))).transpose()?,
+            description: payload.description.map(|d| d.trim().to_string()),
+            invite_link: match payload.invite_link.as_deref().map(str::trim) {
+                Some("") => Some(None),
+                Some(link) => Some(Some(InviteLink::parse(link)?)),
+                None => None,
+            },
+        })
+    }
+}
+
+pub struct NearbyChatsDTO {
+    pub center: GeoPoint,
+    pub radius_m: f64,
+    pub limit: u32,
+}
+
+impl TryFrom<NearbyChatsQuery> for NearbyChatsDTO {
+    type Error = DomainError;
+
+    fn try_from(value: NearbyChatsQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            center: GeoPoint::new(value.lat, value.lon)?,
+            radius_m: value.radius.unwrap_or(DEFAULT_NEARBY_RADIUS_M),
+            limit: value.limit.unwrap_or(DEFAULT_NEARBY_LIMIT),
+        })
+    }
+}
+
+pub struct NearbyChatDTO {
+    pub chat: Chat,
+    pub distance_m: f64,
+}
+
+/// Chats in the location or any location below it, of the category and
+/// carrying every one of the tags.
+#[derive(Default)]
+pub struct ChatFilterDTO {
+    pub location_id: Option<Uuid>,
+    pub category: Option<ChatCategory>,
+    pub tags: Vec<TagName>,
+}
+
+impl TryFrom<ChatsQuery> for ChatFilterDTO {
+    type Error = DomainError;
+
+    fn try_from(value: ChatsQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            location_id: value.location_id,
+            category: value.category.as_deref().map(str::parse).transpose()?,
+            tags: match value.tags.as_deref() {
+                Some(tags) => parse_tags(tags.split(','))?,
+                None => Vec::new(),
+            },
+        })
+    }
+}
+
+/// Chats matching a filter, counted per category and per tag, for faceted
+/// browsing.
+pub struct ChatFacetsDTO {
+    pub categories: Vec<(ChatCategory, i64)>,
+    pub tags: Vec<(TagName, i64)>,
+}
+
+/// A page of members in join order.
+pub struct ListMembersDTO {
+    pub chat_id: Uuid,
+    pub limit: u32,
+    pub cursor: Option<Cursor>,
+}
+
+impl ListMembersDTO {
+    /// The only order members are listed in; cursors are bound to it.
+    pub const SORT: &str = "joined_at";
+
+    pub fn try_new(chat_id: Uuid, query: MembersQuery) -> Result<Self, AppError> {
+        Ok(Self {
+            chat_id,
+            limit: query.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            cursor: query.cursor.map(|c| Cursor::decode(&c, Self::SORT)).transpose().map_err(AppError::Validation)?,
+        })
+    }
+}
+
+pub struct MemberDTO {
+    pub user_id: Uuid,
+    pub display_name: String,
+    pub role: MemberRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// A chat the user belongs to, as seen from the user's side.
+pub struct MembershipDTO {
+    pub chat_id: Uuid,
+    pub chat_name: String,
+    pub role: MemberRole,
+    pub joined_at: DateTime<Utc>,
+}
+
+pub struct TransferOwnershipDTO {
+    pub chat_id: Uuid,
+    pub user_id: Uuid,
+}
+
+/// Chats whose invite links were followed most, counting from `since` (a UTC day).
+pub struct LinkReportDTO {
+    pub since: NaiveDate,
+    pub limit: u32,
+}
+
+impl From<LinkReportQuery> for LinkReportDTO {
+    fn from(value: LinkReportQuery) -> Self {
+        let days = value.days.unwrap_or(DEFAULT_LINK_REPORT_DAYS).max(1);
+
+        Self {
+            // Today counts as the first day
+            since: Utc::now().date_naive() - Days::new(u64::from(days) - 1),
+            limit: value.limit.unwrap_or(DEFAULT_LINK_REPORT_LIMIT),
+        }
+    }
+}
+
+pub struct FollowedChatDTO {
+    pub chat_id: Uuid,
+    pub name: String,
+    pub clicks: i64,
+    pub last_followed_on: NaiveDate,
+}
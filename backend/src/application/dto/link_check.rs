@@ -0,0 +1,18 @@
+use uuid::Uuid;
+
+use crate::domain::entities::{link_check::{LinkCheck, LinkHealth}, value_objects::InviteLink};
+
+/// A live chat's invite link, due for a check.
+pub struct ChatLinkDTO {
+    pub chat_id: Uuid,
+    pub link: InviteLink,
+    /// As left by the previous check, possibly of an older link
+    pub health: Option<LinkHealth>,
+}
+
+pub struct DeadLinkDTO {
+    pub chat_id: Uuid,
+    pub name: String,
+    pub health: LinkHealth,
+    pub last_check: LinkCheck,
+}
@@ -0,0 +1,69 @@
+use uuid::Uuid;
+
+use crate::{
+    adapters::api::location::location_controller::{BoundaryPayload, CreateLocationPayload, UpdateLocationPayload},
+    domain::entities::{boundary::Boundary, chat::Chat, errors::DomainError, location::{District, LocationKind, LocationNode}, value_objects::PlaceName},
+};
+
+pub struct CreateLocationDTO {
+    pub parent_id: Option<Uuid>,
+    pub kind: LocationKind,
+    pub name: PlaceName,
+}
+
+impl TryFrom<CreateLocationPayload> for CreateLocationDTO {
+    type Error = DomainError;
+
+    fn try_from(value: CreateLocationPayload) -> Result<Self, Self::Error> {
+        Ok(Self {
+            parent_id: value.parent_id,
+            kind: value.kind.parse()?,
+            name: PlaceName::parse(&value.name)?,
+        })
+    }
+}
+
+/// Renames and/or moves a location; its kind never changes, so cities stay
+/// roots and everything else keeps some parent.
+pub struct UpdateLocationDTO {
+    pub id: Uuid,
+    /// New parent, `None` keeps the current one
+    pub parent_id: Option<Uuid>,
+    pub name: Option<PlaceName>,
+}
+
+impl UpdateLocationDTO {
+    pub fn try_new(id: Uuid, payload: UpdateLocationPayload) -> Result<Self, DomainError> {
+        Ok(Self {
+            id,
+            parent_id: payload.parent_id,
+            name: payload.name.as_deref().map(PlaceName::parse).transpose()?,
+        })
+    }
+}
+
+pub struct SetBoundaryDTO {
+    pub id: Uuid,
+    pub boundary: Boundary,
+}
+
+impl SetBoundaryDTO {
+    pub fn try_new(id: Uuid, payload: BoundaryPayload) -> Result<Self, DomainError> {
+        Ok(Self {
+            id,
+            boundary: payload.into_geometry().try_into()?,
+        })
+    }
+}
+
+/// The district covering a point, with the chats in it.
+pub struct ResolvedDistrictDTO {
+    pub district: LocationNode,
+    pub chats: Vec<Chat>,
+}
+
+/// Everything drawn on the map: district areas and chats with coordinates.
+pub struct LocationMapDTO {
+    pub districts: Vec<District>,
+    pub chats: Vec<Chat>,
+}
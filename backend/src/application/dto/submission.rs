@@ -0,0 +1,94 @@
+use uuid::Uuid;
+
+use crate::{
+    adapters::api::submission::submission_controller::{ChatProposalPayload, ReviewPayload, SubmissionsQuery},
+    application::dto::chat::CreateChatDTO,
+    domain::entities::{
+        errors::DomainError,
+        submission::{ChatProposal, SubmissionStatus},
+        value_objects::{ChatName, GeoPoint, Location},
+    },
+};
+
+impl TryFrom<ChatProposalPayload> for ChatProposal {
+    type Error = DomainError;
+
+    fn try_from(value: ChatProposalPayload) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: ChatName::parse(&value.name)?,
+            description: value.description.trim().to_string(),
+            location: Location::parse(&value.location)?,
+            position: match (value.latitude, value.longitude) {
+                (Some(lat), Some(lon)) => Some(GeoPoint::new(lat, lon)?),
+                (None, None) => None,
+                _ => return Err(DomainError::CoordinatesInvalid),
+            },
+            location_id: value.location_id,
+            category: value.category.as_deref().map(str::parse).transpose()?.unwrap_or_default(),
+        })
+    }
+}
+
+/// An approved proposal becomes an ordinary chat; tags are left to admins
+/// and the invite link to the new owner.
+impl From<ChatProposal> for CreateChatDTO {
+    fn from(value: ChatProposal) -> Self {
+        Self {
+            name: value.name,
+            description: value.description,
+            location: value.location,
+            position: value.position,
+            location_id: value.location_id,
+            category: value.category,
+            tags: Vec::new(),
+            invite_link: None,
+        }
+    }
+}
+
+pub struct ReviseSubmissionDTO {
+    pub id: Uuid,
+    pub proposal: ChatProposal,
+}
+
+impl ReviseSubmissionDTO {
+    pub fn try_new(id: Uuid, payload: ChatProposalPayload) -> Result<Self, DomainError> {
+        Ok(Self { id, proposal: payload.try_into()? })
+    }
+}
+
+/// A rejection or change request with the reason shown to the submitter.
+pub struct ReviewDTO {
+    pub id: Uuid,
+    pub note: String,
+}
+
+impl ReviewDTO {
+    pub fn new(id: Uuid, payload: ReviewPayload) -> Self {
+        Self { id, note: payload.reason.trim().to_string() }
+    }
+}
+
+/// Submissions in a status, oldest first; `submitter_id` narrows them to
+/// one user's.
+pub struct SubmissionFilterDTO {
+    pub status: Option<SubmissionStatus>,
+    pub submitter_id: Option<Uuid>,
+}
+
+impl SubmissionFilterDTO {
+    pub fn by_submitter(submitter_id: Uuid) -> Self {
+        Self { status: None, submitter_id: Some(submitter_id) }
+    }
+}
+
+impl TryFrom<SubmissionsQuery> for SubmissionFilterDTO {
+    type Error = DomainError;
+
+    fn try_from(value: SubmissionsQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            status: Some(value.status.as_deref().map(str::parse).transpose()?.unwrap_or_default()),
+            submitter_id: None,
+        })
+    }
+}
@@ -0,0 +1,29 @@
+use uuid::Uuid;
+
+use crate::{
+    adapters::api::tag::tag_controller::TagPayload,
+    domain::entities::{errors::DomainError, value_objects::TagName},
+};
+
+pub struct CreateTagDTO {
+    pub name: TagName,
+}
+
+impl TryFrom<TagPayload> for CreateTagDTO {
+    type Error = DomainError;
+
+    fn try_from(value: TagPayload) -> Result<Self, Self::Error> {
+        Ok(Self { name: TagName::parse(&value.name)? })
+    }
+}
+
+pub struct RenameTagDTO {
+    pub id: Uuid,
+    pub name: TagName,
+}
+
+impl RenameTagDTO {
+    pub fn try_new(id: Uuid, payload: TagPayload) -> Result<Self, DomainError> {
+        Ok(Self { id, name: TagName::parse(&payload.name)? })
+    }
+}
@@ -1,15 +1,20 @@
 use secrecy::{ExposeSecret, SecretString};
 
-use crate::{adapters::api::user::user_payload::{DeleteUserByIDPayload, LoginUserPayload, NewUserPayload, UserByIDPayload}, domain::entities::user::{User, UserRole}};
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{adapters::api::user::user_payload::{DeleteUserByIDPayload, ListUsersQuery, LoginUserPayload, NewUserPayload, UserByIDPayload}, application::{AppError, dto::chat::MembershipDTO, pagination::{Cursor, DEFAULT_PAGE_SIZE, SortOrder}, validation::ValidationErrors}, domain::entities::{chat::Chat, errors::DomainError, notification::Notification, submission::ChatSubmission, user::{PrivacySettings, User, UserRole, UserStatus}, value_objects::{DisplayName, Email}}};
 
 pub struct CreateNewUserDTO {
-    pub name: String,
-    pub email: String,
+    pub name: DisplayName,
+    pub email: Email,
     pub password: String,
 }
 
 impl CreateNewUserDTO {
-    pub fn new(name: String, email: String, password: String) -> Self {
+    pub fn new(name: DisplayName, email: Email, password: String) -> Self {
         Self {
             name,
             email,
@@ -22,9 +27,15 @@ impl CreateNewUserDTO {
     }
 }
 
-impl From<NewUserPayload> for CreateNewUserDTO {
-    fn from(value: NewUserPayload) -> Self {
-        Self { name: value.name, email: value.email, password: value.password.expose_secret().to_string() }
+impl TryFrom<NewUserPayload> for CreateNewUserDTO {
+    type Error = DomainError;
+
+    fn try_from(value: NewUserPayload) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: DisplayName::parse(&value.name)?,
+            email: Email::parse(&value.email)?,
+            password: value.password.expose_secret().to_string(),
+        })
     }
 }
 
@@ -48,7 +59,7 @@ impl LoginUserDTO {
 
 impl From<LoginUserPayload> for LoginUserDTO {
     fn from(value: LoginUserPayload) -> Self {
-        Self { email: value.email, password: value.password.expose_secret().to_string() }
+        Self { email: Email::normalize(&value.email), password: value.password.expose_secret().to_string() }
     }
 }
 
@@ -57,32 +68,29 @@ pub struct ResponseUserDTO {
     pub name: String,
     pub email: String,
     pub role: String,
+    pub status: String,
+    pub privacy: PrivacySettings,
 }
 
 impl From<User> for ResponseUserDTO {
     fn from(value: User) -> Self {
-        let role = value.get_role();
-
-        let role_string = {
-            match role {
-                UserRole::Admin => "admin".to_string(),
-                UserRole::User => "user".to_string(),
-            }
-        };
+        let role_string = value.get_role().as_str().to_string();
 
         Self { 
             id: value.get_id().to_string(), 
             name: value.get_name().to_string(), 
             email: value.get_email().to_string(), 
             role: role_string,
+            status: value.get_status().as_str().to_string(),
+            privacy: *value.get_privacy(),
          }
     }
 }
 
 
 impl ResponseUserDTO {
-    pub fn new(id: String, name: String, email: String, role: String) -> Self {
-        Self { id, name, email, role }
+    pub fn new(id: String, name: String, email: String, role: String, status: String, privacy: PrivacySettings) -> Self {
+        Self { id, name, email, role, status, privacy }
     }
 }
 
@@ -94,10 +102,7 @@ pub struct ResponseAuthUserDTO {
 
 impl From<User> for ResponseAuthUserDTO {
     fn from(value: User) -> Self {
-        let role = match value.get_role() {
-            UserRole::Admin => "admin".to_string(),
-            UserRole::User => "user".to_string(),
-        }; 
+        let role = value.get_role().as_str().to_string();
 
         Self { 
             id: value.get_id().to_string(), 
@@ -121,7 +126,7 @@ pub struct LoginResponseDTO {
 
 impl LoginResponseDTO  {
     pub fn new(token: String, user_id: String ) -> Self {
-        Self { token: token, user_id }
+        Self { token, user_id }
     }
 }
 
@@ -164,6 +169,33 @@ impl UpdateUserDTO {
     }
 }
 
+/// Everything stored about one user, for data export requests. Whatever is
+/// added per user elsewhere belongs here too, as it does in anonymisation.
+pub struct UserExportDTO {
+    pub profile: ResponseUserDTO,
+    pub created_at: DateTime<Utc>,
+    pub memberships: Vec<MembershipDTO>,
+    pub submissions: Vec<ChatSubmission>,
+    pub notifications: Vec<Notification>,
+    /// Chats whose details the user edits, invite links included
+    pub owned_chats: Vec<Chat>,
+    /// Favourite chat ids, most recently added first
+    pub favorites: Vec<Uuid>,
+}
+
+impl UserExportDTO {
+    /// The account row alone; the rest is gathered from the other repositories.
+    pub fn new(profile: ResponseUserDTO, created_at: DateTime<Utc>) -> Self {
+        Self { profile, created_at, memberships: Vec::new(), submissions: Vec::new(), notifications: Vec::new(), owned_chats: Vec::new(), favorites: Vec::new() }
+    }
+}
+
+pub struct UpdatePrivacyDTO {
+    pub id: String,
+    pub show_email: Option<bool>,
+    pub show_role: Option<bool>,
+}
+
 pub struct DeleteUserDTO {
     pub id: String,
 }
@@ -179,3 +211,92 @@ impl From<DeleteUserByIDPayload> for DeleteUserDTO {
         DeleteUserDTO { id: value.id }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UserSort {
+    Name,
+    Email,
+    #[default]
+    CreatedAt,
+}
+
+impl UserSort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserSort::Name => "name",
+            UserSort::Email => "email",
+            UserSort::CreatedAt => "created_at",
+        }
+    }
+}
+
+impl FromStr for UserSort {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "name" => Ok(UserSort::Name),
+            "email" => Ok(UserSort::Email),
+            "created_at" => Ok(UserSort::CreatedAt),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct ListUsersDTO {
+    pub limit: u32,
+    pub cursor: Option<Cursor>,
+    /// Lower-cased prefix
+    pub search: Option<String>,
+    /// Directory listing: no email search, and the role filter only matches
+    /// users who show their role
+    pub public: bool,
+    pub role: Option<UserRole>,
+    pub status: Option<UserStatus>,
+    pub sort: UserSort,
+    pub order: SortOrder,
+}
+
+impl Default for ListUsersDTO {
+    fn default() -> Self {
+        Self {
+            limit: DEFAULT_PAGE_SIZE,
+            cursor: None,
+            search: None,
+            public: false,
+            role: None,
+            status: None,
+            sort: UserSort::default(),
+            order: SortOrder::default(),
+        }
+    }
+}
+
+impl TryFrom<ListUsersQuery> for ListUsersDTO {
+    type Error = AppError;
+
+    fn try_from(value: ListUsersQuery) -> Result<Self, Self::Error> {
+        let invalid = |field: &str| AppError::Validation(ValidationErrors::single(field, "invalid_value", format!("{field} has an unsupported value")));
+
+        let sort = match value.sort.as_deref() {
+            Some(sort) => sort.parse().map_err(|_| invalid("sort"))?,
+            None => UserSort::default(),
+        };
+
+        let order = match value.order.as_deref() {
+            Some(order) => order.parse().map_err(|_| invalid("order"))?,
+            None => SortOrder::default(),
+        };
+
+        Ok(Self {
+            limit: value.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            cursor: value.cursor.map(|c| Cursor::decode(&c, sort.as_str())).transpose().map_err(AppError::Validation)?,
+            search: value.q.map(|q| q.trim().to_lowercase()).filter(|q| !q.is_empty()),
+            public: false,
+            role: value.role.map(|r| r.parse()).transpose()?,
+            status: value.status.map(|s| s.parse()).transpose()?,
+            sort,
+            order,
+        })
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use axum::{http::StatusCode, response::IntoResponse};
 
-use crate::{adapters::{api::errors::ClientError, crypto::errors::CryptoError, ctx::CtxError}, domain::entities::errors::DomainError};
+use crate::{adapters::{api::errors::ClientError, crypto::errors::CryptoError, ctx::CtxError, db::postgres::errors::CorruptRow}, application::validation::{FieldError, ValidationErrors}, domain::entities::errors::DomainError};
 
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
@@ -10,6 +10,21 @@ pub enum AppError {
     #[error("Login failed")]
     LoginFail,
 
+    #[error("Password re-entry did not match")]
+    PasswordMismatch,
+
+    // Csrf
+    #[error("Cross-site request check failed")]
+    CsrfCheckFailed,
+
+    // Validation
+    #[error("Request validation failed")]
+    Validation(ValidationErrors),
+
+    // Conflict
+    #[error("Conflict: {0}")]
+    Conflict(Conflict),
+
     // User
     #[error("Can not find user by id")]
     UserNotFoundByID,
@@ -18,6 +33,10 @@ pub enum AppError {
     #[error("Database error")]
     Database(#[from] sqlx::Error),
 
+    // Stored data the domain no longer accepts
+    #[error("Corrupt stored row")]
+    CorruptRow(#[from] CorruptRow),
+
     // Crypto Error
     #[error("Crypto operation failed")]
     Crypt(#[from] CryptoError),
@@ -34,21 +53,137 @@ pub enum AppError {
     #[error("UUID is not valid")]
     UUID(#[from] uuid::Error),
 
+    #[error("Chat not found")]
+    ChatNotFound,
+
+    #[error("Location not found")]
+    LocationNotFound,
+
+    #[error("Tag not found")]
+    TagNotFound,
+
+    #[error("User is not a member of the chat")]
+    NotChatMember,
+
+    #[error("Chat submission not found")]
+    SubmissionNotFound,
+
+    #[error("Notification not found")]
+    NotificationNotFound,
+
+    #[error("Chat has no invite link")]
+    InviteLinkNotSet,
+
     #[error("Internal server error")]
     Internal,
     
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum Conflict {
+    #[error("email is already registered")]
+    EmailTaken,
+
+    #[error("chat with this name already exists in the location")]
+    ChatNameTaken,
+
+    #[error("location with this name already exists under the parent")]
+    LocationNameTaken,
+
+    #[error("location still has child locations or chats")]
+    LocationInUse,
+
+    #[error("tag already exists")]
+    TagNameTaken,
+}
+
 impl AppError {
     pub fn get_client_and_status_code(&self) -> (StatusCode, ClientError) {
+        let client_error = self.client_error();
 
+        (client_error.status(), client_error)
+    }
+
+    pub fn client_error(&self) -> ClientError {
         match self {
-            AppError::LoginFail => (StatusCode::FORBIDDEN, ClientError::LOGIN_FAIL),
-            AppError::Context(_) => (StatusCode::FORBIDDEN, ClientError::NO_AUTH),
-            AppError::UserNotFoundByID => (StatusCode::BAD_REQUEST, ClientError::INVALID_PARAMS),
-            _ => (StatusCode::INTERNAL_SERVER_ERROR, ClientError::SERVICE_ERROR)
+            AppError::LoginFail => ClientError::LOGIN_FAIL,
+            AppError::PasswordMismatch => ClientError::PASSWORD_MISMATCH,
+            AppError::Context(_) => ClientError::NO_AUTH,
+            AppError::CsrfCheckFailed => ClientError::CSRF_FAIL,
+            AppError::UserNotFoundByID | AppError::ChatNotFound | AppError::LocationNotFound | AppError::TagNotFound | AppError::NotChatMember
+            | AppError::SubmissionNotFound | AppError::NotificationNotFound | AppError::InviteLinkNotSet => ClientError::INVALID_PARAMS,
+            AppError::Conflict(Conflict::EmailTaken) => ClientError::EMAIL_TAKEN,
+            AppError::Conflict(Conflict::ChatNameTaken) => ClientError::CHAT_NAME_TAKEN,
+            AppError::Conflict(Conflict::LocationNameTaken) => ClientError::LOCATION_NAME_TAKEN,
+            AppError::Conflict(Conflict::LocationInUse) => ClientError::LOCATION_IN_USE,
+            AppError::Conflict(Conflict::TagNameTaken) => ClientError::TAG_NAME_TAKEN,
+            AppError::Domain(DomainError::OwnerCannotLeave) => ClientError::OWNER_CANNOT_LEAVE,
+            AppError::Domain(DomainError::SubmissionAlreadyDecided) => ClientError::SUBMISSION_DECIDED,
+            AppError::Domain(DomainError::OperationNotPermitted) => ClientError::NO_AUTH,
+            AppError::Validation(_)
+            | AppError::UUID(_)
+            | AppError::Domain(
+                DomainError::EmailValidationFailed
+                | DomainError::DisplayNameInvalid
+                | DomainError::ChatNameInvalid
+                | DomainError::LocationInvalid
+                | DomainError::MemberCountOutOfRange
+                | DomainError::CoordinatesInvalid
+                | DomainError::PlaceNameInvalid
+                | DomainError::InviteLinkInvalid
+                | DomainError::InviteLinkHostNotAllowed(_)
+                | DomainError::TagNameInvalid
+                | DomainError::UnknownCategory(_)
+                | DomainError::UnknownLocationKind(_)
+                | DomainError::LocationHierarchyInvalid
+                | DomainError::BoundaryInvalid(_)
+                | DomainError::BoundaryNotAllowed,
+            ) => ClientError::VALIDATION_ERROR,
+            _ => ClientError::SERVICE_ERROR,
         }
     }
+
+    /// Per-field details for the 422 response body.
+    pub fn field_errors(&self) -> Option<Vec<FieldError>> {
+        let errors = match self {
+            AppError::Validation(errors) => errors.clone(),
+            AppError::UUID(_) => ValidationErrors::single("id", "invalid_uuid", "id must be a UUID"),
+            AppError::Domain(e) => {
+                let (field, code) = match e {
+                    DomainError::EmailValidationFailed => ("email", "invalid_format"),
+                    DomainError::DisplayNameInvalid => ("name", "invalid_length"),
+                    DomainError::ChatNameInvalid => ("name", "invalid_length"),
+                    DomainError::LocationInvalid => ("location", "invalid_length"),
+                    DomainError::MemberCountOutOfRange => ("users_count", "out_of_range"),
+                    DomainError::CoordinatesInvalid => ("coordinates", "out_of_range"),
+                    DomainError::PlaceNameInvalid => ("name", "invalid_length"),
+                    DomainError::InviteLinkInvalid => ("invite_link", "invalid_url"),
+                    DomainError::InviteLinkHostNotAllowed(_) => ("invite_link", "host_not_allowed"),
+                    DomainError::TagNameInvalid => ("tags", "invalid_tag"),
+                    DomainError::UnknownCategory(_) => ("category", "invalid_value"),
+                    DomainError::UnknownLocationKind(_) => ("kind", "invalid_value"),
+                    DomainError::LocationHierarchyInvalid => ("parent_id", "invalid_parent"),
+                    DomainError::BoundaryInvalid(_) => ("geometry", "invalid_geometry"),
+                    DomainError::BoundaryNotAllowed => ("id", "invalid_kind"),
+                    // Only raised while decoding stored values, never for the request
+                    DomainError::UnknownRole(_)
+                    | DomainError::UnknownStatus(_)
+                    | DomainError::UnknownMemberRole(_)
+                    | DomainError::UnknownSubmissionStatus(_)
+                    | DomainError::UnknownNotificationKind(_)
+                    | DomainError::UnknownProbeOutcome(_)
+                    | DomainError::OperationNotPermitted
+                    | DomainError::OwnerCannotLeave
+                    | DomainError::SubmissionAlreadyDecided => return None,
+                };
+
+                ValidationErrors::single(field, code, e.to_string())
+            }
+            _ => return None,
+        };
+
+        Some(errors.errors().to_vec())
+    }
 }
 
 impl IntoResponse for AppError {
@@ -61,3 +196,18 @@ impl IntoResponse for AppError {
         
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corrupt_stored_row_is_not_blamed_on_the_request() {
+        let stored = AppError::from(CorruptRow(DomainError::EmailValidationFailed));
+        let sent = AppError::from(DomainError::EmailValidationFailed);
+
+        assert_eq!(stored.client_error().status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(stored.field_errors().is_none());
+        assert_eq!(sent.client_error().status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
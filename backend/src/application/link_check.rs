@@ -0,0 +1,180 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{TimeDelta, Utc};
+use uuid::Uuid;
+
+use crate::{
+    application::{AppError, AppResult, dto::link_check::DeadLinkDTO, repositories::{link_check::LinkCheckRepo, link_prober::LinkProber}},
+    domain::entities::link_check::{LinkCheck, LinkHealth},
+};
+
+/// Failed checks in a row after which a link counts as dead.
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// Checks older than this are dropped.
+pub const CHECK_HISTORY_DAYS: i64 = 90;
+
+/// What a pass over all invite links found.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct LinkCheckRun {
+    pub checked: usize,
+    pub failed: usize,
+    pub dead: usize,
+}
+
+/// Requests every chat's invite link now and then and keeps track of the ones
+/// that stopped answering.
+pub struct LinkMonitor {
+    repo: Arc<dyn LinkCheckRepo>,
+    prober: Arc<dyn LinkProber>,
+    failure_threshold: u32,
+    pause: Duration,
+}
+
+impl LinkMonitor {
+    pub fn new(repo: Arc<dyn LinkCheckRepo>, prober: Arc<dyn LinkProber>) -> Self {
+        Self {
+            repo,
+            prober,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            pause: Duration::ZERO,
+        }
+    }
+
+    pub fn with_failure_threshold(self, failure_threshold: u32) -> Self {
+        Self { failure_threshold: failure_threshold.max(1), ..self }
+    }
+
+    /// Wait between two requests, so messenger hosts are not flooded.
+    pub fn with_pause(self, pause: Duration) -> Self {
+        Self { pause, ..self }
+    }
+
+    pub async fn check_links(&self) -> AppResult<LinkCheckRun> {
+        let mut run = LinkCheckRun::default();
+
+        for (i, chat) in self.repo.get_chat_links().await?.into_iter().enumerate() {
+            if i > 0 && !self.pause.is_zero() {
+                tokio::time::sleep(self.pause).await;
+            }
+
+            let outcome = self.prober.probe(&chat.link).await;
+            let check = LinkCheck::new(chat.chat_id, chat.link.clone(), outcome);
+
+            let mut health = chat.health.unwrap_or_else(|| LinkHealth::new(chat.link));
+            health.record(&check);
+
+            match self.repo.add_check(&check, &health).await {
+                // Purged while it was being checked
+                Err(AppError::ChatNotFound) => continue,
+                result => result?,
+            }
+
+            run.checked += 1;
+
+            if !check.outcome.is_ok() {
+                run.failed += 1;
+            }
+
+            if health.is_dead(self.failure_threshold) {
+                run.dead += 1;
+            }
+        }
+
+        self.repo.purge_checks(Utc::now() - TimeDelta::days(CHECK_HISTORY_DAYS)).await?;
+
+        Ok(run)
+    }
+
+    pub async fn get_dead_links(&self) -> AppResult<Vec<DeadLinkDTO>> {
+        self.repo.get_dead_links(self.failure_threshold).await
+    }
+
+    /// Newest first.
+    pub async fn get_link_checks(&self, chat_id: Uuid, limit: u32) -> AppResult<Vec<LinkCheck>> {
+        self.repo.get_checks(chat_id, limit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Mutex};
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+
+    use crate::{
+        adapters::http::prober::StubLinkProber,
+        application::dto::link_check::ChatLinkDTO,
+        domain::entities::{link_check::ProbeOutcome, value_objects::InviteLink},
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MemoryLinkCheckRepo {
+        links: Vec<(Uuid, InviteLink)>,
+        health: Mutex<HashMap<Uuid, LinkHealth>>,
+        checks: Mutex<Vec<LinkCheck>>,
+    }
+
+    #[async_trait]
+    impl LinkCheckRepo for MemoryLinkCheckRepo {
+        async fn get_chat_links(&self) -> AppResult<Vec<ChatLinkDTO>> {
+            let health = self.health.lock().unwrap();
+
+            Ok(self
+                .links
+                .iter()
+                .map(|(chat_id, link)| ChatLinkDTO { chat_id: *chat_id, link: link.clone(), health: health.get(chat_id).cloned() })
+                .collect())
+        }
+
+        async fn add_check(&self, check: &LinkCheck, health: &LinkHealth) -> AppResult<()> {
+            self.checks.lock().unwrap().push(check.clone());
+            self.health.lock().unwrap().insert(check.chat_id, health.clone());
+
+            Ok(())
+        }
+
+        async fn get_checks(&self, chat_id: Uuid, limit: u32) -> AppResult<Vec<LinkCheck>> {
+            Ok(self.checks.lock().unwrap().iter().rev().filter(|c| c.chat_id == chat_id).take(limit as usize).cloned().collect())
+        }
+
+        async fn get_dead_links(&self, _failure_threshold: u32) -> AppResult<Vec<DeadLinkDTO>> {
+            Ok(Vec::new())
+        }
+
+        async fn purge_checks(&self, _before: DateTime<Utc>) -> AppResult<u64> {
+            Ok(0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_links_failing_repeatedly_are_counted_dead() {
+        let (alive, gone) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let repo = Arc::new(MemoryLinkCheckRepo {
+            links: vec![
+                (alive, InviteLink::parse("https://t.me/alive").unwrap()),
+                (gone, InviteLink::parse("https://t.me/gone").unwrap()),
+            ],
+            ..Default::default()
+        });
+
+        let prober = Arc::new(StubLinkProber::default());
+        prober.set("https://t.me/alive", ProbeOutcome::from_status(200));
+        prober.set("https://t.me/gone", ProbeOutcome::from_status(404));
+
+        let monitor = LinkMonitor::new(repo.clone(), prober.clone()).with_failure_threshold(2);
+
+        assert_eq!(monitor.check_links().await.unwrap(), LinkCheckRun { checked: 2, failed: 1, dead: 0 });
+        assert_eq!(monitor.check_links().await.unwrap(), LinkCheckRun { checked: 2, failed: 1, dead: 1 });
+
+        // Back up again
+        prober.set("https://t.me/gone", ProbeOutcome::from_status(200));
+        assert_eq!(monitor.check_links().await.unwrap(), LinkCheckRun { checked: 2, failed: 0, dead: 0 });
+
+        assert_eq!(monitor.get_link_checks(gone, 10).await.unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,111 @@
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::application::validation::ValidationErrors;
+
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "asc" => Ok(SortOrder::Asc),
+            "desc" => Ok(SortOrder::Desc),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Keyset position: the sort key and id of the last row of the previous
+/// page. Opaque to clients and only valid for the sort it was issued for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    pub sort: String,
+    pub key: String,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        base64_url::encode(&format!("{}|{}|{}", self.sort, self.id, self.key))
+    }
+
+    pub fn decode(value: &str, sort: &str) -> Result<Self, ValidationErrors> {
+        let invalid = || ValidationErrors::single("cursor", "invalid_cursor", "cursor is malformed or belongs to another sort");
+
+        let bytes = base64_url::decode(value).map_err(|_| invalid())?;
+        let raw = String::from_utf8(bytes).map_err(|_| invalid())?;
+
+        let mut parts = raw.splitn(3, '|');
+
+        let (Some(cursor_sort), Some(id), Some(key)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(invalid());
+        };
+
+        if cursor_sort != sort {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            sort: cursor_sort.to_string(),
+            key: key.to_string(),
+            id: Uuid::parse_str(id).map_err(|_| invalid())?,
+        })
+    }
+}
+
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Matching rows across all pages
+    pub total: i64,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip_is_bound_to_sort() {
+        let cursor = Cursor {
+            sort: "name".to_string(),
+            key: "anna|b".to_string(),
+            id: Uuid::new_v4(),
+        };
+
+        let encoded = cursor.encode();
+
+        assert_eq!(Cursor::decode(&encoded, "name").unwrap(), cursor);
+        assert!(Cursor::decode(&encoded, "created_at").is_err());
+        assert!(Cursor::decode("not a cursor", "name").is_err());
+    }
+}
@@ -1,3 +1,10 @@
 pub mod user;
 pub mod chat;
+pub mod location;
+pub mod tag;
+pub mod submission;
+pub mod notification;
+pub mod favorite;
+pub mod link_check;
+pub mod link_prober;
 pub mod hash;
\ No newline at end of file
@@ -1,10 +1,38 @@
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
 
-use crate::{adapters::api::chat::chat_controller::CreateChatPayload, application::AppResult, domain::entities::chat::Chat};
+use crate::{application::{AppResult, dto::chat::{ChatFacetsDTO, ChatFilterDTO, CreateChatDTO, FollowedChatDTO, LinkReportDTO, ListMembersDTO, MemberDTO, MembershipDTO, NearbyChatDTO, NearbyChatsDTO, UpdateChatDTO}, pagination::Page, retention::Deleted}, domain::entities::{chat::Chat, member::ChatMember}};
 
 #[async_trait]
 pub trait ChatRepo: Send + Sync {
-    async fn add_chat(&self, payload: CreateChatPayload) -> AppResult<Chat>;
-    async fn get_chats(&self) -> AppResult<Vec<Chat>>;
+    async fn add_chat(&self, chat_dto: CreateChatDTO) -> AppResult<Chat>;
+    async fn get_chats(&self, filter: ChatFilterDTO) -> AppResult<Vec<Chat>>;
+    /// Live chats only; fails with `ChatNotFound` otherwise.
+    async fn get_chat(&self, id: Uuid) -> AppResult<Chat>;
+    /// Live chats among `ids`, in no particular order.
+    async fn get_chats_by_ids(&self, ids: &[Uuid]) -> AppResult<Vec<Chat>>;
+    /// Fails with `TagNotFound` if a tag is not in the vocabulary.
+    async fn update_chat(&self, chat_dto: UpdateChatDTO) -> AppResult<Chat>;
+    async fn get_facets(&self, filter: ChatFilterDTO) -> AppResult<ChatFacetsDTO>;
     async fn delete_chat_by_id(&self, id: String) -> AppResult<()>;
-}
\ No newline at end of file
+    /// Chats with coordinates within `radius_m`, closest first.
+    async fn find_nearby(&self, query: NearbyChatsDTO) -> AppResult<Vec<NearbyChatDTO>>;
+    async fn get_deleted_chats(&self, since: DateTime<Utc>) -> AppResult<Vec<Deleted<Chat>>>;
+    async fn restore_chat(&self, id: String, since: DateTime<Utc>) -> AppResult<Chat>;
+    async fn get_member(&self, chat_id: Uuid, user_id: Uuid) -> AppResult<Option<ChatMember>>;
+    /// Keeps an existing membership, including its role, as it is.
+    async fn add_member(&self, member: ChatMember) -> AppResult<ChatMember>;
+    /// Makes the user the owner, joining them if needed; the previous owner
+    /// becomes a plain member.
+    async fn set_owner(&self, chat_id: Uuid, user_id: Uuid) -> AppResult<()>;
+    async fn remove_member(&self, chat_id: Uuid, user_id: Uuid) -> AppResult<()>;
+    /// Chats the user belongs to, deleted ones included, oldest first.
+    async fn get_memberships(&self, user_id: Uuid) -> AppResult<Vec<MembershipDTO>>;
+    async fn list_members(&self, query: ListMembersDTO) -> AppResult<Page<MemberDTO>>;
+    /// Counts one follow of the chat's invite link on `day`.
+    async fn record_link_click(&self, chat_id: Uuid, day: NaiveDate) -> AppResult<()>;
+    /// Live chats only, most clicks first.
+    async fn most_followed(&self, query: LinkReportDTO) -> AppResult<Vec<FollowedChatDTO>>;
+    async fn purge_chats(&self, before: DateTime<Utc>) -> AppResult<u64>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::application::AppResult;
+
+#[async_trait]
+pub trait FavoriteRepo: Send + Sync {
+    /// Adding a favourite again keeps it where it was.
+    async fn add_favorite(&self, user_id: Uuid, chat_id: Uuid) -> AppResult<()>;
+    /// A no-op for chats that are not favourites.
+    async fn remove_favorite(&self, user_id: Uuid, chat_id: Uuid) -> AppResult<()>;
+    /// Chat ids, most recently added first.
+    async fn get_favorites(&self, user_id: Uuid) -> AppResult<Vec<Uuid>>;
+}
@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{application::{AppResult, dto::link_check::{ChatLinkDTO, DeadLinkDTO}}, domain::entities::link_check::{LinkCheck, LinkHealth}};
+
+#[async_trait]
+pub trait LinkCheckRepo: Send + Sync {
+    /// Live chats with an invite link, least recently checked first.
+    async fn get_chat_links(&self) -> AppResult<Vec<ChatLinkDTO>>;
+    /// Stores the check along with the health it left the link in.
+    async fn add_check(&self, check: &LinkCheck, health: &LinkHealth) -> AppResult<()>;
+    /// Newest first.
+    async fn get_checks(&self, chat_id: Uuid, limit: u32) -> AppResult<Vec<LinkCheck>>;
+    /// Live chats whose current link failed at least `failure_threshold`
+    /// times in a row, most failures first.
+    async fn get_dead_links(&self, failure_threshold: u32) -> AppResult<Vec<DeadLinkDTO>>;
+    async fn purge_checks(&self, before: DateTime<Utc>) -> AppResult<u64>;
+}
@@ -0,0 +1,10 @@
+use async_trait::async_trait;
+
+use crate::domain::entities::{link_check::ProbeOutcome, value_objects::InviteLink};
+
+/// Requests an invite link to tell whether the group behind it still exists.
+#[async_trait]
+pub trait LinkProber: Send + Sync {
+    /// Never fails: anything that prevents an answer is `Unreachable`.
+    async fn probe(&self, link: &InviteLink) -> ProbeOutcome;
+}
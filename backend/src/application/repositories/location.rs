@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{application::AppResult, domain::entities::{boundary::Boundary, location::{District, LocationNode}, value_objects::GeoPoint}};
+
+#[async_trait]
+pub trait LocationRepo: Send + Sync {
+    async fn create_location(&self, location: LocationNode) -> AppResult<LocationNode>;
+    async fn get_location(&self, id: Uuid) -> AppResult<LocationNode>;
+    /// The whole tree as a flat list; it is small enough to load at once.
+    async fn get_locations(&self) -> AppResult<Vec<LocationNode>>;
+    async fn update_location(&self, location: LocationNode) -> AppResult<LocationNode>;
+    /// Fails with `LocationInUse` while child locations or live chats refer to it.
+    async fn delete_location(&self, id: Uuid) -> AppResult<()>;
+    /// Stores or, with `None`, removes the area of a district.
+    async fn set_boundary(&self, id: Uuid, boundary: Option<&Boundary>) -> AppResult<()>;
+    async fn get_districts(&self) -> AppResult<Vec<District>>;
+    /// Districts whose bounding box holds `point`; the caller checks the exact shape.
+    async fn find_districts_around(&self, point: GeoPoint) -> AppResult<Vec<District>>;
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{application::AppResult, domain::entities::notification::Notification};
+
+#[async_trait]
+pub trait NotificationRepo: Send + Sync {
+    /// Newest first.
+    async fn get_notifications(&self, user_id: Uuid) -> AppResult<Vec<Notification>>;
+    /// Only the recipient can mark a notification; marking twice is a no-op.
+    async fn mark_read(&self, user_id: Uuid, id: Uuid) -> AppResult<()>;
+}
@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    application::{AppResult, dto::submission::SubmissionFilterDTO},
+    domain::entities::{notification::Notification, submission::ChatSubmission},
+};
+
+#[async_trait]
+pub trait SubmissionRepo: Send + Sync {
+    async fn add_submission(&self, submission: ChatSubmission) -> AppResult<ChatSubmission>;
+    async fn get_submission(&self, id: Uuid) -> AppResult<ChatSubmission>;
+    async fn get_submissions(&self, filter: SubmissionFilterDTO) -> AppResult<Vec<ChatSubmission>>;
+    /// Stores a new proposal, unless the submission was decided meanwhile.
+    async fn revise_submission(&self, submission: ChatSubmission) -> AppResult<ChatSubmission>;
+    /// Stores the decision on a still pending submission together with the
+    /// notification to its submitter. Approval also lists the chat under the
+    /// submission's `chat_id` with the submitter as its owner, all or nothing.
+    async fn decide_submission(&self, submission: ChatSubmission, notification: Notification) -> AppResult<ChatSubmission>;
+}
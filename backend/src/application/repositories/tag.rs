@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::{application::AppResult, domain::entities::tag::Tag};
+
+#[async_trait]
+pub trait TagRepo: Send + Sync {
+    async fn create_tag(&self, tag: Tag) -> AppResult<Tag>;
+    async fn get_tags(&self) -> AppResult<Vec<Tag>>;
+    async fn rename_tag(&self, tag: Tag) -> AppResult<Tag>;
+    /// Also removes the tag from every chat.
+    async fn delete_tag(&self, id: Uuid) -> AppResult<()>;
+}
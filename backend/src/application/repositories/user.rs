@@ -1,6 +1,7 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
-use crate::{application::{AppResult, dto::user::{CreateNewUserDTO, DeleteUserDTO, GetUserByIdDTO, GetUserByEmailDTO}}, domain::entities::user::User};
+use crate::{application::{AppResult, dto::user::{CreateNewUserDTO, DeleteUserDTO, GetUserByIdDTO, GetUserByEmailDTO, ListUsersDTO, UpdatePrivacyDTO, UserExportDTO}, pagination::Page, retention::Deleted}, domain::entities::user::User};
 
 #[async_trait]
 pub trait UserRepository: Send + Sync {
@@ -12,6 +13,24 @@ pub trait UserRepository: Send + Sync {
 
     async fn get_users(&self) -> AppResult<Vec<User>>;
 
+    async fn list_users(&self, query: ListUsersDTO) -> AppResult<Page<User>>;
+
+    async fn update_privacy(&self, user_dto: UpdatePrivacyDTO) -> AppResult<User>;
+
     async fn delete_user_by_id(&self, user_dto: DeleteUserDTO) -> AppResult<()>;
 
+    /// Replaces personal data with placeholders and revokes credentials.
+    async fn anonymise_user(&self, id: &str) -> AppResult<()>;
+
+    async fn export_user(&self, id: &str) -> AppResult<UserExportDTO>;
+
+    /// Users deleted after `since`, newest first.
+    async fn get_deleted_users(&self, since: DateTime<Utc>) -> AppResult<Vec<Deleted<User>>>;
+
+    /// Clears the tombstone of a user deleted after `since`.
+    async fn restore_user(&self, id: &str, since: DateTime<Utc>) -> AppResult<User>;
+
+    /// Hard-deletes users deleted before `before`, returning how many.
+    async fn purge_users(&self, before: DateTime<Utc>) -> AppResult<u64>;
+
 }
\ No newline at end of file
@@ -0,0 +1,42 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// How long deleted users and chats can be restored before they are purged.
+pub const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+/// A soft-deleted entity and when it was deleted.
+pub struct Deleted<T> {
+    pub item: T,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl<T> Deleted<T> {
+    pub fn restorable_until(&self, retention: TimeDelta) -> DateTime<Utc> {
+        self.deleted_at + retention
+    }
+}
+
+/// What happens to a user's data when they close their account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AccountDeletion {
+    /// Scrub personal data but keep the row, so references from other
+    /// records stay valid
+    #[default]
+    Anonymise,
+    /// Delete the row like an admin would: restorable until the retention
+    /// window ends, then purged along with dependent rows
+    Erase,
+}
+
+impl FromStr for AccountDeletion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "anonymise" | "anonymize" => Ok(AccountDeletion::Anonymise),
+            "erase" => Ok(AccountDeletion::Erase),
+            other => Err(format!("unknown account deletion policy {other:?}")),
+        }
+    }
+}
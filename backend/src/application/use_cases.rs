@@ -1,59 +1,100 @@
-use std::sync::Arc;
+use std::{collections::HashSet, str::FromStr, sync::Arc};
 
+use chrono::{DateTime, TimeDelta, Utc};
 use secrecy::ExposeSecret;
 
 use crate::{
-    adapters::{api::chat::{chat_controller::CreateChatPayload, chat_presenter::ChatPresenter}, crypto::token::{Token, generate_token, validate_token}},
+    adapters::crypto::token::{Token, generate_token, validate_token},
     application::{
         AppError, AppResult,
+        dto::chat::{ChatFacetsDTO, ChatFilterDTO, CreateChatDTO, FollowedChatDTO, LinkReportDTO, ListMembersDTO, MemberDTO, NearbyChatDTO, NearbyChatsDTO, TransferOwnershipDTO, UpdateChatDTO},
+        dto::tag::{CreateTagDTO, RenameTagDTO},
+        dto::submission::{ReviewDTO, ReviseSubmissionDTO, SubmissionFilterDTO},
+        dto::location::{CreateLocationDTO, LocationMapDTO, ResolvedDistrictDTO, SetBoundaryDTO, UpdateLocationDTO},
+        pagination::Page,
+        retention::{AccountDeletion, DEFAULT_RETENTION_DAYS, Deleted},
+        validation::ValidationErrors,
         dto::user::{
-            CreateNewUserDTO, DeleteUserDTO, GetUserByEmailDTO, GetUserByIdDTO, LoginResponseDTO, LoginUserDTO, ResponseAuthUserDTO, ResponseUserDTO
+            CreateNewUserDTO, DeleteUserDTO, GetUserByEmailDTO, GetUserByIdDTO, LoginResponseDTO, UserExportDTO, ListUsersDTO, LoginUserDTO, UpdatePrivacyDTO, ResponseAuthUserDTO, ResponseUserDTO, UserSort
         },
-        repositories::{chat::ChatRepo, hash::Hasher, user::UserRepository},
-    }, domain::entities::chat::Chat,
+        repositories::{chat::ChatRepo, favorite::FavoriteRepo, hash::Hasher, location::LocationRepo, notification::NotificationRepo, submission::SubmissionRepo, tag::TagRepo, user::UserRepository},
+    }, domain::entities::{chat::Chat, errors::DomainError, location::{District, LocationNode}, member::{ChatMember, MemberRole}, notification::Notification, submission::{ChatProposal, ChatSubmission}, tag::Tag, user::{Actor, UserStatus}, value_objects::{GeoPoint, InviteLink, LinkAllowList}},
 };
+use uuid::Uuid;
 
 pub struct UseCases {
     user_repo: Arc<dyn UserRepository>,
     chat_repo: Arc<dyn ChatRepo>,
+    location_repo: Arc<dyn LocationRepo>,
+    tag_repo: Arc<dyn TagRepo>,
+    submission_repo: Arc<dyn SubmissionRepo>,
+    notification_repo: Arc<dyn NotificationRepo>,
+    favorite_repo: Arc<dyn FavoriteRepo>,
     hasher: Arc<dyn Hasher>,
+    account_deletion: AccountDeletion,
+    retention: TimeDelta,
+    link_allow_list: LinkAllowList,
 }
 
 impl UseCases {
+    // One per repository; settings go through the `with_*` builders instead
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_repo: Arc<dyn UserRepository>,
         chat_repo: Arc<dyn ChatRepo>,
+        location_repo: Arc<dyn LocationRepo>,
+        tag_repo: Arc<dyn TagRepo>,
+        submission_repo: Arc<dyn SubmissionRepo>,
+        notification_repo: Arc<dyn NotificationRepo>,
+        favorite_repo: Arc<dyn FavoriteRepo>,
         hasher: Arc<dyn Hasher>,
     ) -> Self {
         Self {
             user_repo,
             chat_repo,
+            location_repo,
+            tag_repo,
+            submission_repo,
+            notification_repo,
+            favorite_repo,
             hasher,
+            account_deletion: AccountDeletion::default(),
+            retention: TimeDelta::days(DEFAULT_RETENTION_DAYS),
+            link_allow_list: LinkAllowList::default(),
         }
     }
 
+    pub fn with_retention(self, retention: TimeDelta) -> Self {
+        Self { retention, ..self }
+    }
+
+    /// How long deleted users and chats stay restorable.
+    pub fn retention(&self) -> TimeDelta {
+        self.retention
+    }
+
+    fn retention_cutoff(&self) -> DateTime<Utc> {
+        Utc::now() - self.retention
+    }
+
+    pub fn with_account_deletion(self, account_deletion: AccountDeletion) -> Self {
+        Self { account_deletion, ..self }
+    }
+
+    /// Messenger hosts new invite links must point to.
+    pub fn with_link_allow_list(self, link_allow_list: LinkAllowList) -> Self {
+        Self { link_allow_list, ..self }
+    }
+
     pub async fn create_user(&self, user_dto: CreateNewUserDTO) -> AppResult<ResponseUserDTO> {
         let password_hash = self.hasher.hash(user_dto.password.to_owned()).await?;
 
         let user_dto_with_password_hash = user_dto.with_password_hash(password_hash);
 
-        let result = self
+        let user = self
             .user_repo
             .create_user(user_dto_with_password_hash)
-            .await;
-
-        let user = match result {
-            Ok(user) => {
-                if let Err(e) = user.validate_email() {
-                    println!("failed to create new user: {}", e);
-
-                    return Err(AppError::Domain(e));
-                }
-
-                user
-            }
-            Err(e) => return Err(e),
-        };
+            .await?;
 
         let response_dto = user.into();
         Ok(response_dto)
@@ -73,6 +114,11 @@ impl UseCases {
     ) -> AppResult<ResponseAuthUserDTO> {
         let user = self.user_repo.get_user_by_id(user_dto).await?;
 
+        // Session tokens are stateless, so closing an account must end them here
+        if *user.get_status() == UserStatus::Closed {
+            return Err(AppError::UserNotFoundByID);
+        }
+
         let response_dto = user.into();
 
         Ok(response_dto)
@@ -92,6 +138,75 @@ impl UseCases {
         Ok(response_dto_vec)
     }
 
+    pub async fn list_users(&self, query: ListUsersDTO) -> AppResult<Page<ResponseUserDTO>> {
+        let page = self.user_repo.list_users(query).await?;
+
+        Ok(page.map(|u| u.into()))
+    }
+
+    /// Directory view for non-admins: active accounts only, and neither
+    /// searching nor sorting may reveal anything about email addresses.
+    pub async fn list_public_users(&self, mut query: ListUsersDTO) -> AppResult<Page<ResponseUserDTO>> {
+        if query.sort == UserSort::Email {
+            return Err(AppError::Validation(ValidationErrors::single("sort", "invalid_value", "sort has an unsupported value")));
+        }
+
+        query.status = Some(UserStatus::Active);
+        query.public = true;
+
+        self.list_users(query).await
+    }
+
+    pub async fn update_privacy(&self, user_dto: UpdatePrivacyDTO) -> AppResult<ResponseUserDTO> {
+        let user = self.user_repo.update_privacy(user_dto).await?;
+
+        Ok(user.into())
+    }
+
+    /// Closes the caller's own account after checking their password again.
+    pub async fn delete_own_account(&self, user_id: &str, password: String) -> AppResult<()> {
+        let user = self.user_repo.get_user_by_id(GetUserByIdDTO::new(user_id.to_string())).await?;
+
+        self.hasher
+            .validate(password, user.get_password_hash().expose_secret().to_owned())
+            .await
+            .map_err(|_| AppError::PasswordMismatch)?;
+
+        // Closing an account leaves every chat, so the owner rule applies here too
+        let id = Uuid::from_str(user_id)?;
+
+        for membership in self.chat_repo.get_memberships(id).await? {
+            ChatMember::new(membership.chat_id, id, membership.role).check_leave()?;
+        }
+
+        match self.account_deletion {
+            AccountDeletion::Anonymise => self.user_repo.anonymise_user(user_id).await,
+            AccountDeletion::Erase => self.user_repo.delete_user_by_id(DeleteUserDTO::new(user_id.to_string())).await,
+        }
+    }
+
+    pub async fn export_account(&self, user_id: &str) -> AppResult<UserExportDTO> {
+        let export = self.user_repo.export_user(user_id).await?;
+        let user_id = Uuid::from_str(user_id)?;
+
+        let memberships = self.chat_repo.get_memberships(user_id).await?;
+
+        let owned: Vec<Uuid> = memberships
+            .iter()
+            .filter(|m| m.role == MemberRole::Owner)
+            .map(|m| m.chat_id)
+            .collect();
+
+        Ok(UserExportDTO {
+            owned_chats: self.chat_repo.get_chats_by_ids(&owned).await?,
+            favorites: self.favorite_repo.get_favorites(user_id).await?,
+            memberships,
+            submissions: self.submission_repo.get_submissions(SubmissionFilterDTO::by_submitter(user_id)).await?,
+            notifications: self.notification_repo.get_notifications(user_id).await?,
+            ..export
+        })
+    }
+
     pub async fn login_user(&self, user_dto: LoginUserDTO) -> AppResult<LoginResponseDTO> {
         let user_email = user_dto.email.clone();
         let user_by_email_dto = GetUserByEmailDTO { email: user_email };
@@ -119,29 +234,329 @@ impl UseCases {
         Ok(())
     }
 
-    pub async fn add_new_chat(&self, payload: CreateChatPayload) -> AppResult<ChatPresenter> {
-        let chat = self.chat_repo.add_chat(payload).await?;
+    pub async fn add_new_chat(&self, chat_dto: CreateChatDTO) -> AppResult<Chat> {
+        if let Some(link) = &chat_dto.invite_link {
+            self.link_allow_list.check(link)?;
+        }
 
-        let chat_present = ChatPresenter{
-            id: chat.id,
-            name: chat.name,
-            description: chat.description,
-            users_count: chat.users_count,
-            location: chat.location,
-        };
+        let chat = self.chat_repo.add_chat(chat_dto).await?;
+
+        Ok(chat)
+    }
+
+    pub async fn get_chats(&self, filter: ChatFilterDTO) -> AppResult<Vec<Chat>> {
+        if let Some(location_id) = filter.location_id {
+            self.location_repo.get_location(location_id).await?;
+        }
+
+        let chats = self.chat_repo.get_chats(filter).await?;
+
+        Ok(chats)
+    }
+
+    pub async fn update_chat(&self, actor: Actor, chat_dto: UpdateChatDTO) -> AppResult<Chat> {
+        let chat = self.chat_repo.get_chat(chat_dto.id).await?;
+
+        for action in chat_dto.actions() {
+            chat.authorize(&actor, action)?;
+        }
+
+        if let Some(Some(link)) = &chat_dto.invite_link {
+            self.link_allow_list.check(link)?;
+        }
+
+        self.chat_repo.update_chat(chat_dto).await
+    }
+
+    pub async fn transfer_chat_ownership(&self, actor: Actor, transfer: TransferOwnershipDTO) -> AppResult<Chat> {
+        let mut chat = self.chat_repo.get_chat(transfer.chat_id).await?;
+
+        // Authorised first, so others cannot probe which user ids exist
+        let previous_owner = chat.transfer_ownership(&actor, transfer.user_id)?;
+
+        self.user_repo
+            .get_user_by_id(GetUserByIdDTO::new(transfer.user_id.to_string()))
+            .await
+            .map_err(|_| AppError::UserNotFoundByID)?;
+
+        if previous_owner != Some(transfer.user_id) {
+            self.chat_repo.set_owner(transfer.chat_id, transfer.user_id).await?;
+        }
 
-        Ok(chat_present)
+        self.chat_repo.get_chat(transfer.chat_id).await
     }
 
-    pub async fn get_chats(&self) -> AppResult<Vec<Chat>> {
-        let chats = self.chat_repo.get_chats().await?;
+    /// The chat's invite link, counting the follow.
+    pub async fn follow_invite_link(&self, chat_id: Uuid) -> AppResult<InviteLink> {
+        let chat = self.chat_repo.get_chat(chat_id).await?;
+
+        let link = chat.invite_link.ok_or(AppError::InviteLinkNotSet)?;
+
+        self.chat_repo.record_link_click(chat_id, Utc::now().date_naive()).await?;
+
+        Ok(link)
+    }
+
+    pub async fn get_most_followed_chats(&self, query: LinkReportDTO) -> AppResult<Vec<FollowedChatDTO>> {
+        self.chat_repo.most_followed(query).await
+    }
+
+    pub async fn get_chat_facets(&self, filter: ChatFilterDTO) -> AppResult<ChatFacetsDTO> {
+        if let Some(location_id) = filter.location_id {
+            self.location_repo.get_location(location_id).await?;
+        }
+
+        self.chat_repo.get_facets(filter).await
+    }
+
+    pub async fn join_chat(&self, chat_id: Uuid, user_id: &str) -> AppResult<Chat> {
+        let user_id = Uuid::from_str(user_id)?;
+
+        self.chat_repo.get_chat(chat_id).await?;
+        self.chat_repo.add_member(ChatMember::new(chat_id, user_id, MemberRole::Member)).await?;
 
+        // Again, for the count including the new member
+
+        self.chat_repo.get_chat(chat_id).await
+    }
+
+    pub async fn leave_chat(&self, chat_id: Uuid, user_id: &str) -> AppResult<()> {
+        let user_id = Uuid::from_str(user_id)?;
+
+        self.chat_repo.get_chat(chat_id).await?;
+
+        let member = self.chat_repo.get_member(chat_id, user_id).await?.ok_or(AppError::NotChatMember)?;
+
+        member.check_leave()?;
+
+        self.chat_repo.remove_member(chat_id, user_id).await
+    }
+
+    pub async fn add_favorite(&self, user_id: &str, chat_id: Uuid) -> AppResult<Chat> {
+        let user_id = Uuid::from_str(user_id)?;
+
+        let chat = self.chat_repo.get_chat(chat_id).await?;
+
+        self.favorite_repo.add_favorite(user_id, chat_id).await?;
+
+        Ok(chat)
+    }
+
+    pub async fn remove_favorite(&self, user_id: &str, chat_id: Uuid) -> AppResult<()> {
+        let user_id = Uuid::from_str(user_id)?;
+
+        self.favorite_repo.remove_favorite(user_id, chat_id).await
+    }
+
+    /// Most recently added first; deleted chats are left out.
+    pub async fn get_favorite_chats(&self, user_id: &str) -> AppResult<Vec<Chat>> {
+        let user_id = Uuid::from_str(user_id)?;
+
+        let ids = self.favorite_repo.get_favorites(user_id).await?;
+
+        let mut chats = self.chat_repo.get_chats_by_ids(&ids).await?;
+        chats.sort_by_key(|c| ids.iter().position(|id| *id == c.id));
 
         Ok(chats)
     }
 
-    pub async fn get_chats_filtered(&self) -> AppResult<Vec<Chat>> {
-        todo!()
+    pub async fn get_favorite_chat_ids(&self, user_id: Uuid) -> AppResult<HashSet<Uuid>> {
+        let ids = self.favorite_repo.get_favorites(user_id).await?;
+
+        Ok(ids.into_iter().collect())
+    }
+
+    pub async fn list_chat_members(&self, query: ListMembersDTO) -> AppResult<Page<MemberDTO>> {
+        self.chat_repo.get_chat(query.chat_id).await?;
+
+        self.chat_repo.list_members(query).await
+    }
+
+    pub async fn submit_chat(&self, user_id: &str, proposal: ChatProposal) -> AppResult<ChatSubmission> {
+        let user = self.user_repo.get_user_by_id(GetUserByIdDTO::new(user_id.to_string())).await?;
+
+        if let Some(location_id) = proposal.location_id {
+            self.location_repo.get_location(location_id).await?;
+        }
+
+        let submission = user.submit_chat(proposal)?;
+
+        self.submission_repo.add_submission(submission).await
+    }
+
+    pub async fn get_own_submissions(&self, user_id: &str) -> AppResult<Vec<ChatSubmission>> {
+        let user_id = Uuid::from_str(user_id)?;
+
+        self.submission_repo.get_submissions(SubmissionFilterDTO::by_submitter(user_id)).await
+    }
+
+    pub async fn revise_submission(&self, user_id: &str, submission_dto: ReviseSubmissionDTO) -> AppResult<ChatSubmission> {
+        let user_id = Uuid::from_str(user_id)?;
+
+        let mut submission = self.submission_repo.get_submission(submission_dto.id).await?;
+
+        submission.revise(user_id, submission_dto.proposal)?;
+
+        self.submission_repo.revise_submission(submission).await
+    }
+
+    pub async fn get_submission_queue(&self, filter: SubmissionFilterDTO) -> AppResult<Vec<ChatSubmission>> {
+        self.submission_repo.get_submissions(filter).await
+    }
+
+    pub async fn approve_submission(&self, id: Uuid) -> AppResult<ChatSubmission> {
+        let mut submission = self.submission_repo.get_submission(id).await?;
+
+        submission.approve(Uuid::new_v4())?;
+
+        self.decide_submission(submission).await
+    }
+
+    pub async fn reject_submission(&self, review: ReviewDTO) -> AppResult<ChatSubmission> {
+        let mut submission = self.submission_repo.get_submission(review.id).await?;
+
+        submission.reject(review.note)?;
+
+        self.decide_submission(submission).await
+    }
+
+    pub async fn request_submission_changes(&self, review: ReviewDTO) -> AppResult<ChatSubmission> {
+        let mut submission = self.submission_repo.get_submission(review.id).await?;
+
+        submission.request_changes(review.note)?;
+
+        self.decide_submission(submission).await
+    }
+
+    async fn decide_submission(&self, submission: ChatSubmission) -> AppResult<ChatSubmission> {
+        let notification = Notification::for_decision(&submission).ok_or(AppError::Internal)?;
+
+        self.submission_repo.decide_submission(submission, notification).await
+    }
+
+    pub async fn get_notifications(&self, user_id: &str) -> AppResult<Vec<Notification>> {
+        self.notification_repo.get_notifications(Uuid::from_str(user_id)?).await
+    }
+
+    pub async fn mark_notification_read(&self, user_id: &str, id: Uuid) -> AppResult<()> {
+        self.notification_repo.mark_read(Uuid::from_str(user_id)?, id).await
+    }
+
+    pub async fn create_tag(&self, tag_dto: CreateTagDTO) -> AppResult<Tag> {
+        self.tag_repo.create_tag(Tag::new(None, tag_dto.name)).await
+    }
+
+    pub async fn get_tags(&self) -> AppResult<Vec<Tag>> {
+        self.tag_repo.get_tags().await
+    }
+
+    pub async fn rename_tag(&self, tag_dto: RenameTagDTO) -> AppResult<Tag> {
+        self.tag_repo.rename_tag(Tag::new(Some(tag_dto.id), tag_dto.name)).await
+    }
+
+    pub async fn delete_tag(&self, id: Uuid) -> AppResult<()> {
+        self.tag_repo.delete_tag(id).await
+    }
+
+    pub async fn find_nearby_chats(&self, query: NearbyChatsDTO) -> AppResult<Vec<NearbyChatDTO>> {
+        self.chat_repo.find_nearby(query).await
+    }
+
+    pub async fn create_location(&self, location_dto: CreateLocationDTO) -> AppResult<LocationNode> {
+        let parent = match location_dto.parent_id {
+            Some(parent_id) => Some(self.location_repo.get_location(parent_id).await?),
+            None => None,
+        };
+
+        LocationNode::check_placement(location_dto.kind, parent.as_ref())?;
+
+        let location = LocationNode::new(None, location_dto.parent_id, location_dto.kind, location_dto.name);
+
+        self.location_repo.create_location(location).await
+    }
+
+    pub async fn get_locations(&self) -> AppResult<Vec<LocationNode>> {
+        self.location_repo.get_locations().await
+    }
+
+    /// Renames and/or moves a node together with its subtree.
+    pub async fn update_location(&self, location_dto: UpdateLocationDTO) -> AppResult<LocationNode> {
+        let nodes = self.location_repo.get_locations().await?;
+
+        let mut location = nodes
+            .iter()
+            .find(|n| n.id == location_dto.id)
+            .cloned()
+            .ok_or(AppError::LocationNotFound)?;
+
+        if let Some(parent_id) = location_dto.parent_id
+            && location.parent_id != Some(parent_id)
+        {
+            let parent = nodes.iter().find(|n| n.id == parent_id).ok_or(AppError::LocationNotFound)?;
+
+            LocationNode::check_placement(location.kind, Some(parent))?;
+
+            if parent.descends_from(location.id, &nodes) {
+                return Err(DomainError::LocationHierarchyInvalid.into());
+            }
+
+            location.parent_id = Some(parent_id);
+        }
+
+        if let Some(name) = location_dto.name {
+            location.name = name;
+        }
+
+        self.location_repo.update_location(location).await
+    }
+
+    pub async fn delete_location(&self, id: Uuid) -> AppResult<()> {
+        self.location_repo.delete_location(id).await
+    }
+
+    pub async fn set_location_boundary(&self, boundary_dto: SetBoundaryDTO) -> AppResult<District> {
+        let location = self.location_repo.get_location(boundary_dto.id).await?;
+
+        let district = District::new(location, boundary_dto.boundary)?;
+
+        self.location_repo.set_boundary(district.location.id, Some(&district.boundary)).await?;
+
+        Ok(district)
+    }
+
+    pub async fn clear_location_boundary(&self, id: Uuid) -> AppResult<()> {
+        self.location_repo.set_boundary(id, None).await
+    }
+
+    /// Finds the district covering `point`; where districts overlap the first
+    /// by name wins.
+    pub async fn resolve_district(&self, point: GeoPoint) -> AppResult<Option<ResolvedDistrictDTO>> {
+        let district = self
+            .location_repo
+            .find_districts_around(point)
+            .await?
+            .into_iter()
+            .find(|d| d.boundary.contains(&point));
+
+        let Some(district) = district else {
+            return Ok(None);
+        };
+
+        let chats = self
+            .chat_repo
+            .get_chats(ChatFilterDTO { location_id: Some(district.location.id), ..Default::default() })
+            .await?;
+
+        Ok(Some(ResolvedDistrictDTO { district: district.location, chats }))
+    }
+
+    pub async fn get_location_map(&self) -> AppResult<LocationMapDTO> {
+        let districts = self.location_repo.get_districts().await?;
+
+        let mut chats = self.chat_repo.get_chats(ChatFilterDTO::default()).await?;
+        chats.retain(|c| c.position.is_some());
+
+        Ok(LocationMapDTO { districts, chats })
     }
 
     pub async fn delete_chat_by_id(&self, id: String) -> AppResult<()> {
@@ -150,4 +565,38 @@ impl UseCases {
 
         Ok(())
     }
+
+    pub async fn get_deleted_users(&self) -> AppResult<Vec<Deleted<ResponseUserDTO>>> {
+        let users = self.user_repo.get_deleted_users(self.retention_cutoff()).await?;
+
+        Ok(users
+            .into_iter()
+            .map(|d| Deleted { item: d.item.into(), deleted_at: d.deleted_at })
+            .collect())
+    }
+
+    pub async fn restore_user(&self, id: String) -> AppResult<ResponseUserDTO> {
+        let user = self.user_repo.restore_user(&id, self.retention_cutoff()).await?;
+
+        Ok(user.into())
+    }
+
+    pub async fn get_deleted_chats(&self) -> AppResult<Vec<Deleted<Chat>>> {
+        self.chat_repo.get_deleted_chats(self.retention_cutoff()).await
+    }
+
+    pub async fn restore_chat(&self, id: String) -> AppResult<Chat> {
+        self.chat_repo.restore_chat(id, self.retention_cutoff()).await
+    }
+
+    /// Hard-deletes whatever has outlived the retention window; returns the
+    /// number of purged users and chats.
+    pub async fn purge_deleted(&self) -> AppResult<(u64, u64)> {
+        let cutoff = self.retention_cutoff();
+
+        let users = self.user_repo.purge_users(cutoff).await?;
+        let chats = self.chat_repo.purge_chats(cutoff).await?;
+
+        Ok((users, chats))
+    }
 }
@@ -0,0 +1,179 @@
+use std::str::FromStr;
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::entities::value_objects::Email;
+
+#[derive(Debug, Clone, Serialize, PartialEq, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    #[schema(value_type = String)]
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// Collects every failing field instead of stopping at the first one, so a
+/// form can highlight all problems in a single round trip.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn single(field: &str, code: &'static str, message: impl Into<String>) -> Self {
+        let mut errors = Self::new();
+
+        errors.add(field, code, message);
+
+        errors
+    }
+
+    pub fn add(&mut self, field: &str, code: &'static str, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field: field.to_string(),
+            code,
+            message: message.into(),
+        });
+    }
+
+    pub fn extend(&mut self, other: ValidationErrors) {
+        self.errors.extend(other.errors);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+
+    // Common rules
+
+    pub fn required(&mut self, field: &str, value: &str) -> bool {
+        if value.trim().is_empty() {
+            self.add(field, "required", format!("{field} must not be empty"));
+
+            return false;
+        }
+
+        true
+    }
+
+    pub fn max_len(&mut self, field: &str, value: &str, max: usize) {
+        if value.chars().count() > max {
+            self.add(field, "too_long", format!("{field} must be at most {max} characters"));
+        }
+    }
+
+    pub fn min_len(&mut self, field: &str, value: &str, min: usize) {
+        if value.chars().count() < min {
+            self.add(field, "too_short", format!("{field} must be at least {min} characters"));
+        }
+    }
+
+    pub fn email(&mut self, field: &str, value: &str) {
+        if Email::parse(value).is_err() {
+            self.add(field, "invalid_format", format!("{field} must be a valid email address"));
+        }
+    }
+
+    pub fn uuid(&mut self, field: &str, value: &str) {
+        if uuid::Uuid::parse_str(value).is_err() {
+            self.add(field, "invalid_uuid", format!("{field} must be a UUID"));
+        }
+    }
+
+    pub fn parse<T: FromStr>(&mut self, field: &str, value: &str) -> Option<T> {
+        let parsed = value.parse().ok();
+
+        if parsed.is_none() {
+            self.add(field, "invalid_value", format!("{field} has an unsupported value"));
+        }
+
+        parsed
+    }
+
+    pub fn range(&mut self, field: &str, value: u32, min: u32, max: u32) {
+        if !(min..=max).contains(&value) {
+            self.add(field, "out_of_range", format!("{field} must be between {min} and {max}"));
+        }
+    }
+
+    pub fn between(&mut self, field: &str, value: f64, min: f64, max: f64) {
+        if !(min..=max).contains(&value) {
+            self.add(field, "out_of_range", format!("{field} must be between {min} and {max}"));
+        }
+    }
+
+    /// Optional coordinates must come as a complete, in-range pair.
+    pub fn coordinates(&mut self, latitude: Option<f64>, longitude: Option<f64>) {
+        match (latitude, longitude) {
+            (Some(lat), Some(lon)) => {
+                self.between("latitude", lat, -90.0, 90.0);
+                self.between("longitude", lon, -180.0, 180.0);
+            }
+            (Some(_), None) => self.add("longitude", "required", "longitude is required with latitude"),
+            (None, Some(_)) => self.add("latitude", "required", "latitude is required with longitude"),
+            (None, None) => {}
+        }
+    }
+
+    pub fn non_negative(&mut self, field: &str, value: i64) {
+        if value < 0 {
+            self.add(field, "out_of_range", format!("{field} must not be negative"));
+        }
+    }
+}
+
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collects_all_field_errors() {
+        let mut errors = ValidationErrors::new();
+
+        errors.required("name", "  ");
+        errors.email("email", "not-an-email");
+        errors.min_len("password", "short", 8);
+        errors.uuid("id", "42");
+
+        let codes: Vec<_> = errors.errors().iter().map(|e| (e.field.as_str(), e.code)).collect();
+
+        assert_eq!(
+            codes,
+            vec![
+                ("name", "required"),
+                ("email", "invalid_format"),
+                ("password", "too_short"),
+                ("id", "invalid_uuid"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_valid_values_pass() {
+        let mut errors = ValidationErrors::new();
+
+        errors.required("name", "Anna");
+        errors.email("email", "anna@example.org");
+        errors.max_len("name", "Anna", 64);
+        errors.non_negative("users_count", 0);
+
+        assert!(errors.into_result().is_ok());
+    }
+}
@@ -1,3 +1,11 @@
+pub mod boundary;
 pub mod chat;
 pub mod user;
-pub mod errors;
\ No newline at end of file
+pub mod errors;
+pub mod link_check;
+pub mod location;
+pub mod member;
+pub mod notification;
+pub mod submission;
+pub mod tag;
+pub mod value_objects;
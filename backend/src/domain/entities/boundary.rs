@@ -0,0 +1,129 @@
+use crate::domain::entities::{errors::{DomainError, DomainResult}, value_objects::GeoPoint};
+
+/// Area covered by a district: one or more polygons, each an outer ring
+/// followed by optional holes. Rings are closed, i.e. the first point is
+/// repeated at the end, as in GeoJSON.
+///
+/// Edges are treated as straight lines in latitude/longitude, which is
+/// accurate enough at city scale.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Boundary {
+    polygons: Vec<Vec<Vec<GeoPoint>>>,
+}
+
+impl Boundary {
+    /// Upper limit on the points over all rings, to keep lookups cheap.
+    pub const MAX_POINTS: usize = 20_000;
+
+    pub fn new(polygons: Vec<Vec<Vec<GeoPoint>>>) -> DomainResult<Self> {
+        let invalid = |reason: &str| Err(DomainError::BoundaryInvalid(reason.to_string()));
+
+        if polygons.is_empty() {
+            return invalid("at least one polygon is required");
+        }
+
+        if polygons.iter().flatten().map(Vec::len).sum::<usize>() > Self::MAX_POINTS {
+            return invalid("too many points");
+        }
+
+        for polygon in &polygons {
+            if polygon.is_empty() {
+                return invalid("a polygon needs an outer ring");
+            }
+
+            for ring in polygon {
+                if ring.len() < 4 {
+                    return invalid("a ring needs at least four points");
+                }
+
+                if ring.first() != ring.last() {
+                    return invalid("a ring must end where it starts");
+                }
+
+                if signed_area(ring) == 0.0 {
+                    return invalid("a ring must enclose an area");
+                }
+            }
+        }
+
+        Ok(Self { polygons })
+    }
+
+    pub fn polygons(&self) -> &[Vec<Vec<GeoPoint>>] {
+        &self.polygons
+    }
+
+    /// Whether `point` lies inside an outer ring and outside its holes.
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        self.polygons.iter().any(|polygon| {
+            let (outer, holes) = polygon.split_first().expect("checked in Boundary::new");
+
+            ring_contains(outer, point) && !holes.iter().any(|hole| ring_contains(hole, point))
+        })
+    }
+
+    /// `(min_lat, max_lat, min_lon, max_lon)` of all outer rings.
+    pub fn bounding_box(&self) -> (f64, f64, f64, f64) {
+        self.polygons
+            .iter()
+            .filter_map(|polygon| polygon.first())
+            .flatten()
+            .fold((f64::MAX, f64::MIN, f64::MAX, f64::MIN), |(min_lat, max_lat, min_lon, max_lon), p| {
+                (min_lat.min(p.lat()), max_lat.max(p.lat()), min_lon.min(p.lon()), max_lon.max(p.lon()))
+            })
+    }
+}
+
+/// Shoelace formula in degrees²; only its sign and zero-ness matter here.
+fn signed_area(ring: &[GeoPoint]) -> f64 {
+    ring.windows(2)
+        .map(|w| w[0].lon() * w[1].lat() - w[1].lon() * w[0].lat())
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Even-odd ray casting towards increasing longitude.
+fn ring_contains(ring: &[GeoPoint], point: &GeoPoint) -> bool {
+    let (x, y) = (point.lon(), point.lat());
+
+    ring.windows(2).fold(false, |inside, w| {
+        let (a, b) = (&w[0], &w[1]);
+
+        let crosses = (a.lat() > y) != (b.lat() > y)
+            && x < (b.lon() - a.lon()) * (y - a.lat()) / (b.lat() - a.lat()) + a.lon();
+
+        inside != crosses
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring(points: &[(f64, f64)]) -> Vec<GeoPoint> {
+        points.iter().map(|&(lat, lon)| GeoPoint::new(lat, lon).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_contains_respects_holes() {
+        let outer = ring(&[(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0), (0.0, 0.0)]);
+        let hole = ring(&[(4.0, 4.0), (4.0, 6.0), (6.0, 6.0), (6.0, 4.0), (4.0, 4.0)]);
+
+        let boundary = Boundary::new(vec![vec![outer, hole]]).unwrap();
+
+        assert!(boundary.contains(&GeoPoint::new(2.0, 2.0).unwrap()));
+        assert!(!boundary.contains(&GeoPoint::new(5.0, 5.0).unwrap()));
+        assert!(!boundary.contains(&GeoPoint::new(11.0, 5.0).unwrap()));
+        assert_eq!(boundary.bounding_box(), (0.0, 10.0, 0.0, 10.0));
+    }
+
+    #[test]
+    fn test_rejects_open_or_flat_rings() {
+        let open = ring(&[(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]);
+        let flat = ring(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (0.0, 0.0)]);
+
+        assert!(Boundary::new(vec![]).is_err());
+        assert!(Boundary::new(vec![vec![open]]).is_err());
+        assert!(Boundary::new(vec![vec![flat]]).is_err());
+    }
+}
@@ -1,22 +1,92 @@
-use serde::Serialize;
+use std::str::FromStr;
+
 use uuid::Uuid;
 
-#[derive(Debug, Serialize)]
+use crate::domain::entities::{errors::{DomainError, DomainResult}, user::Actor, value_objects::{ChatName, GeoPoint, InviteLink, Location, MemberCount, TagName}};
+
+/// Fixed top-level grouping; finer distinctions are left to tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChatCategory {
+    #[default]
+    General,
+    Neighbours,
+    Education,
+    Pets,
+    Sport,
+    Marketplace,
+    Transport,
+    Events,
+}
+
+impl ChatCategory {
+    pub const ALL: [ChatCategory; 8] = [
+        ChatCategory::General,
+        ChatCategory::Neighbours,
+        ChatCategory::Education,
+        ChatCategory::Pets,
+        ChatCategory::Sport,
+        ChatCategory::Marketplace,
+        ChatCategory::Transport,
+        ChatCategory::Events,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChatCategory::General => "general",
+            ChatCategory::Neighbours => "neighbours",
+            ChatCategory::Education => "education",
+            ChatCategory::Pets => "pets",
+            ChatCategory::Sport => "sport",
+            ChatCategory::Marketplace => "marketplace",
+            ChatCategory::Transport => "transport",
+            ChatCategory::Events => "events",
+        }
+    }
+}
+
+impl FromStr for ChatCategory {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> DomainResult<Self> {
+        ChatCategory::ALL
+            .into_iter()
+            .find(|c| c.as_str() == s)
+            .ok_or_else(|| DomainError::UnknownCategory(s.to_string()))
+    }
+}
+
+/// Changes to a listed chat, as checked by `Chat::authorize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatAction {
+    /// Description and invite link
+    EditDetails,
+    /// Category and tags, which shape the directory as a whole
+    Reclassify,
+    TransferOwnership,
+}
+
+#[derive(Debug)]
 pub struct Chat {
     pub id: Uuid,
-    pub name: String,
+    pub name: ChatName,
     pub description: String,
-    pub users_count: u64,
-    pub location: String,
+    pub users_count: MemberCount,
+    pub location: Location,
+    pub position: Option<GeoPoint>,
+    pub location_id: Option<Uuid>,
+    pub category: ChatCategory,
+    pub tags: Vec<TagName>,
+    pub owner_id: Option<Uuid>,
+    pub invite_link: Option<InviteLink>,
 }
 
 impl Chat {
     pub fn new(
         id: Option<Uuid>,
-        name: String,
+        name: ChatName,
         description: String,
-        users_count: u64,
-        location: String,
+        users_count: MemberCount,
+        location: Location,
     ) -> Self {
         let id = match id  {
             Some(id) => id,
@@ -25,7 +95,81 @@ impl Chat {
             }
         };
 
-        Self{ id, name, description, users_count, location }
+        Self{ id, name, description, users_count, location, position: None, location_id: None, category: ChatCategory::default(), tags: Vec::new(), owner_id: None, invite_link: None }
+    }
+
+    pub fn with_position(self, position: Option<GeoPoint>) -> Self {
+        Self { position, ..self }
+    }
+
+    pub fn with_location_id(self, location_id: Option<Uuid>) -> Self {
+        Self { location_id, ..self }
+    }
+
+    pub fn with_category(self, category: ChatCategory) -> Self {
+        Self { category, ..self }
+    }
+
+    pub fn with_tags(self, tags: Vec<TagName>) -> Self {
+        Self { tags, ..self }
+    }
+
+    pub fn with_owner_id(self, owner_id: Option<Uuid>) -> Self {
+        Self { owner_id, ..self }
+    }
+
+    pub fn with_invite_link(self, invite_link: Option<InviteLink>) -> Self {
+        Self { invite_link, ..self }
+    }
+
+    /// Site admins may do anything; the owner may edit the details and hand
+    /// the chat over, but not reclassify it.
+    pub fn authorize(&self, actor: &Actor, action: ChatAction) -> DomainResult<()> {
+        let is_owner = self.owner_id == Some(actor.user_id);
+
+        let allowed = actor.is_admin()
+            || match action {
+                ChatAction::EditDetails | ChatAction::TransferOwnership => is_owner,
+                ChatAction::Reclassify => false,
+            };
+
+        if !allowed {
+            return Err(DomainError::OperationNotPermitted);
+        }
+
+        Ok(())
+    }
+
+    /// Makes `new_owner` the owner and returns the previous one, if any.
+    pub fn transfer_ownership(&mut self, actor: &Actor, new_owner: Uuid) -> DomainResult<Option<Uuid>> {
+        self.authorize(actor, ChatAction::TransferOwnership)?;
+
+        Ok(self.owner_id.replace(new_owner))
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::user::UserRole;
+
+    #[test]
+    fn test_owner_edits_but_does_not_reclassify() {
+        let owner = Actor { user_id: Uuid::new_v4(), role: UserRole::User };
+        let stranger = Actor { user_id: Uuid::new_v4(), role: UserRole::User };
+        let admin = Actor { user_id: Uuid::new_v4(), role: UserRole::Admin };
+
+        let mut chat = Chat::new(None, ChatName::parse("Yard").unwrap(), String::new(), MemberCount::default(), Location::parse("Main st").unwrap())
+            .with_owner_id(Some(owner.user_id));
+
+        assert!(chat.authorize(&owner, ChatAction::EditDetails).is_ok());
+        assert!(chat.authorize(&owner, ChatAction::Reclassify).is_err());
+        assert!(chat.authorize(&stranger, ChatAction::EditDetails).is_err());
+        assert!(chat.authorize(&admin, ChatAction::Reclassify).is_ok());
+
+        assert!(chat.transfer_ownership(&stranger, stranger.user_id).is_err());
+        assert_eq!(chat.transfer_ownership(&owner, stranger.user_id).unwrap(), Some(owner.user_id));
+        assert!(chat.authorize(&owner, ChatAction::EditDetails).is_err());
     }
-    
 }
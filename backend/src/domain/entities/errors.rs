@@ -3,8 +3,74 @@ pub enum DomainError {
     #[error("Email has invalid format")]
     EmailValidationFailed,
 
+    #[error("Display name is empty or too long")]
+    DisplayNameInvalid,
+
+    #[error("Chat name is empty or too long")]
+    ChatNameInvalid,
+
+    #[error("Location is empty or too long")]
+    LocationInvalid,
+
+    #[error("Member count is out of range")]
+    MemberCountOutOfRange,
+
+    #[error("Invite link must be an https address")]
+    InviteLinkInvalid,
+
+    #[error("Invite links to {0} are not accepted")]
+    InviteLinkHostNotAllowed(String),
+
+    #[error("Place name is empty or too long")]
+    PlaceNameInvalid,
+
+    #[error("Tag is empty, too long or contains a comma")]
+    TagNameInvalid,
+
+    #[error("Unknown chat category: {0}")]
+    UnknownCategory(String),
+
+    #[error("Unknown location kind: {0}")]
+    UnknownLocationKind(String),
+
+    #[error("Location cannot be placed under this parent")]
+    LocationHierarchyInvalid,
+
+    #[error("District boundary is invalid: {0}")]
+    BoundaryInvalid(String),
+
+    #[error("Only districts can have a boundary")]
+    BoundaryNotAllowed,
+
+    #[error("Latitude must be within ±90 and longitude within ±180 degrees")]
+    CoordinatesInvalid,
+
+    #[error("Unknown user role: {0}")]
+    UnknownRole(String),
+
+    #[error("Unknown user status: {0}")]
+    UnknownStatus(String),
+
+    #[error("Unknown member role: {0}")]
+    UnknownMemberRole(String),
+
+    #[error("The owner cannot leave the chat")]
+    OwnerCannotLeave,
+
+    #[error("Unknown submission status: {0}")]
+    UnknownSubmissionStatus(String),
+
+    #[error("The submission has already been decided")]
+    SubmissionAlreadyDecided,
+
+    #[error("Unknown notification kind: {0}")]
+    UnknownNotificationKind(String),
+
+    #[error("Unknown link probe outcome: {0}")]
+    UnknownProbeOutcome(String),
+
     #[error("Operation not permitted")]
     OperationNotPermitted
 }
 
-pub type DomainResult<T> = Result<T, DomainError>;
\ No newline at end of file
+pub type DomainResult<T> = Result<T, DomainError>;
@@ -0,0 +1,157 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::{errors::{DomainError, DomainResult}, value_objects::InviteLink};
+
+/// What requesting an invite link gave.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProbeOutcome {
+    /// Answered with a success status, possibly after redirects
+    Ok { status: u16 },
+    /// Answered with an error status
+    Broken { status: u16 },
+    /// No answer at all: DNS, TLS, connection or timeout
+    Unreachable { error: String },
+}
+
+impl ProbeOutcome {
+    pub fn from_status(status: u16) -> Self {
+        if (200..400).contains(&status) {
+            ProbeOutcome::Ok { status }
+        } else {
+            ProbeOutcome::Broken { status }
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        matches!(self, ProbeOutcome::Ok { .. })
+    }
+
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ProbeOutcome::Ok { status } | ProbeOutcome::Broken { status } => Some(*status),
+            ProbeOutcome::Unreachable { .. } => None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            ProbeOutcome::Unreachable { error } => Some(error),
+            _ => None,
+        }
+    }
+
+    pub fn kind(&self) -> ProbeKind {
+        match self {
+            ProbeOutcome::Ok { .. } => ProbeKind::Ok,
+            ProbeOutcome::Broken { .. } => ProbeKind::Broken,
+            ProbeOutcome::Unreachable { .. } => ProbeKind::Unreachable,
+        }
+    }
+}
+
+/// `ProbeOutcome` without its details, as stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeKind {
+    Ok,
+    Broken,
+    Unreachable,
+}
+
+impl ProbeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProbeKind::Ok => "ok",
+            ProbeKind::Broken => "broken",
+            ProbeKind::Unreachable => "unreachable",
+        }
+    }
+}
+
+impl FromStr for ProbeKind {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> DomainResult<Self> {
+        match s {
+            "ok" => Ok(ProbeKind::Ok),
+            "broken" => Ok(ProbeKind::Broken),
+            "unreachable" => Ok(ProbeKind::Unreachable),
+            other => Err(DomainError::UnknownProbeOutcome(other.to_string())),
+        }
+    }
+}
+
+/// One request of a chat's invite link.
+#[derive(Debug, Clone)]
+pub struct LinkCheck {
+    pub chat_id: Uuid,
+    pub link: InviteLink,
+    pub outcome: ProbeOutcome,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl LinkCheck {
+    pub fn new(chat_id: Uuid, link: InviteLink, outcome: ProbeOutcome) -> Self {
+        Self { chat_id, link, outcome, checked_at: Utc::now() }
+    }
+}
+
+/// How reliably a chat's current invite link has answered lately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkHealth {
+    pub link: InviteLink,
+    /// Failed checks in a row since the link last answered
+    pub failures: u32,
+}
+
+impl LinkHealth {
+    pub fn new(link: InviteLink) -> Self {
+        Self { link, failures: 0 }
+    }
+
+    /// Counts the check; a check of another link starts over, since the
+    /// owner has replaced the one that kept failing.
+    pub fn record(&mut self, check: &LinkCheck) {
+        if check.link != self.link {
+            *self = Self::new(check.link.clone());
+        }
+
+        self.failures = if check.outcome.is_ok() { 0 } else { self.failures.saturating_add(1) };
+    }
+
+    pub fn is_dead(&self, failure_threshold: u32) -> bool {
+        self.failures >= failure_threshold.max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failures_count_in_a_row_and_reset_with_a_new_link() {
+        let chat_id = Uuid::new_v4();
+        let old = InviteLink::parse("https://t.me/old").unwrap();
+        let new = InviteLink::parse("https://t.me/new").unwrap();
+
+        let mut health = LinkHealth::new(old.clone());
+
+        for outcome in [ProbeOutcome::from_status(404), ProbeOutcome::Unreachable { error: "timeout".to_string() }] {
+            health.record(&LinkCheck::new(chat_id, old.clone(), outcome));
+        }
+
+        assert_eq!(health.failures, 2);
+        assert!(!health.is_dead(3));
+
+        health.record(&LinkCheck::new(chat_id, old.clone(), ProbeOutcome::from_status(500)));
+        assert!(health.is_dead(3));
+
+        health.record(&LinkCheck::new(chat_id, new.clone(), ProbeOutcome::from_status(404)));
+        assert_eq!((health.link.clone(), health.failures), (new.clone(), 1));
+
+        health.record(&LinkCheck::new(chat_id, new, ProbeOutcome::from_status(302)));
+        assert_eq!(health.failures, 0);
+    }
+}
@@ -0,0 +1,130 @@
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::domain::entities::{boundary::Boundary, errors::{DomainError, DomainResult}, value_objects::PlaceName};
+
+/// Level in the location tree; a child is always of a finer kind than its parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LocationKind {
+    City,
+    District,
+    Street,
+    Building,
+}
+
+impl LocationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LocationKind::City => "city",
+            LocationKind::District => "district",
+            LocationKind::Street => "street",
+            LocationKind::Building => "building",
+        }
+    }
+}
+
+impl FromStr for LocationKind {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> DomainResult<Self> {
+        match s {
+            "city" => Ok(LocationKind::City),
+            "district" => Ok(LocationKind::District),
+            "street" => Ok(LocationKind::Street),
+            "building" => Ok(LocationKind::Building),
+            other => Err(DomainError::UnknownLocationKind(other.to_string())),
+        }
+    }
+}
+
+/// Node of the location tree (city, district, street, building).
+#[derive(Debug, Clone)]
+pub struct LocationNode {
+    pub id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub kind: LocationKind,
+    pub name: PlaceName,
+}
+
+impl LocationNode {
+    pub fn new(id: Option<Uuid>, parent_id: Option<Uuid>, kind: LocationKind, name: PlaceName) -> Self {
+        Self { id: id.unwrap_or_else(Uuid::new_v4), parent_id, kind, name }
+    }
+
+    /// Checks placing a node of `kind` under `parent`; only cities may be roots.
+    pub fn check_placement(kind: LocationKind, parent: Option<&LocationNode>) -> DomainResult<()> {
+        match parent {
+            None if kind == LocationKind::City => Ok(()),
+            Some(parent) if parent.kind < kind => Ok(()),
+            _ => Err(DomainError::LocationHierarchyInvalid),
+        }
+    }
+
+    /// Whether `ancestor_id` is this node or one of its ancestors in `nodes`.
+    pub fn descends_from(&self, ancestor_id: Uuid, nodes: &[LocationNode]) -> bool {
+        let mut current = Some(self.id);
+
+        // Bounded by the tree size, so a corrupt cycle cannot loop forever
+        for _ in 0..=nodes.len() {
+            match current {
+                Some(id) if id == ancestor_id => return true,
+                Some(id) => current = nodes.iter().find(|n| n.id == id).and_then(|n| n.parent_id),
+                None => return false,
+            }
+        }
+
+        false
+    }
+}
+
+/// A district together with the area it covers.
+#[derive(Debug, Clone)]
+pub struct District {
+    pub location: LocationNode,
+    pub boundary: Boundary,
+}
+
+impl District {
+    pub fn new(location: LocationNode, boundary: Boundary) -> DomainResult<Self> {
+        if location.kind != LocationKind::District {
+            return Err(DomainError::BoundaryNotAllowed);
+        }
+
+        Ok(Self { location, boundary })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(kind: LocationKind, parent: Option<&LocationNode>) -> LocationNode {
+        LocationNode::new(None, parent.map(|p| p.id), kind, PlaceName::parse(kind.as_str()).unwrap())
+    }
+
+    #[test]
+    fn test_placement_follows_kind_order() {
+        let city = node(LocationKind::City, None);
+        let street = node(LocationKind::Street, Some(&city));
+
+        assert!(LocationNode::check_placement(LocationKind::City, None).is_ok());
+        assert!(LocationNode::check_placement(LocationKind::District, None).is_err());
+        assert!(LocationNode::check_placement(LocationKind::Building, Some(&street)).is_ok());
+        assert!(LocationNode::check_placement(LocationKind::District, Some(&street)).is_err());
+        assert!(LocationNode::check_placement(LocationKind::City, Some(&city)).is_err());
+    }
+
+    #[test]
+    fn test_descends_from() {
+        let city = node(LocationKind::City, None);
+        let district = node(LocationKind::District, Some(&city));
+        let building = node(LocationKind::Building, Some(&district));
+
+        let nodes = vec![city.clone(), district.clone(), building.clone()];
+
+        assert!(building.descends_from(city.id, &nodes));
+        assert!(district.descends_from(district.id, &nodes));
+        assert!(!city.descends_from(district.id, &nodes));
+    }
+}
@@ -0,0 +1,75 @@
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::domain::entities::errors::{DomainError, DomainResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberRole {
+    Owner,
+    Moderator,
+    Member,
+}
+
+impl MemberRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MemberRole::Owner => "owner",
+            MemberRole::Moderator => "moderator",
+            MemberRole::Member => "member",
+        }
+    }
+}
+
+impl FromStr for MemberRole {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> DomainResult<Self> {
+        match s {
+            "owner" => Ok(MemberRole::Owner),
+            "moderator" => Ok(MemberRole::Moderator),
+            "member" => Ok(MemberRole::Member),
+            other => Err(DomainError::UnknownMemberRole(other.to_string())),
+        }
+    }
+}
+
+/// A user's membership in a chat.
+#[derive(Debug, Clone)]
+pub struct ChatMember {
+    pub chat_id: Uuid,
+    pub user_id: Uuid,
+    pub role: MemberRole,
+}
+
+impl ChatMember {
+    pub fn new(chat_id: Uuid, user_id: Uuid, role: MemberRole) -> Self {
+        Self { chat_id, user_id, role }
+    }
+
+    /// Owners hand the chat over before leaving, so it is never left without one.
+    pub fn check_leave(&self) -> DomainResult<()> {
+        if self.role == MemberRole::Owner {
+            return Err(DomainError::OwnerCannotLeave);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_owner_is_kept_from_leaving() {
+        let (chat_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert!(ChatMember::new(chat_id, user_id, MemberRole::Member).check_leave().is_ok());
+        assert!(ChatMember::new(chat_id, user_id, MemberRole::Moderator).check_leave().is_ok());
+        assert!(matches!(
+            ChatMember::new(chat_id, user_id, MemberRole::Owner).check_leave(),
+            Err(DomainError::OwnerCannotLeave)
+        ));
+    }
+}
@@ -0,0 +1,72 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::{errors::{DomainError, DomainResult}, submission::{ChatSubmission, SubmissionStatus}};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    SubmissionApproved,
+    SubmissionRejected,
+    SubmissionChangesRequested,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::SubmissionApproved => "submission_approved",
+            NotificationKind::SubmissionRejected => "submission_rejected",
+            NotificationKind::SubmissionChangesRequested => "submission_changes_requested",
+        }
+    }
+}
+
+impl FromStr for NotificationKind {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> DomainResult<Self> {
+        match s {
+            "submission_approved" => Ok(NotificationKind::SubmissionApproved),
+            "submission_rejected" => Ok(NotificationKind::SubmissionRejected),
+            "submission_changes_requested" => Ok(NotificationKind::SubmissionChangesRequested),
+            other => Err(DomainError::UnknownNotificationKind(other.to_string())),
+        }
+    }
+}
+
+/// In-app message to a single user.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: NotificationKind,
+    pub submission_id: Option<Uuid>,
+    /// Free text from whoever caused the notification
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+impl Notification {
+    /// Tells the submitter how their submission was decided. `None` while it
+    /// is still pending.
+    pub fn for_decision(submission: &ChatSubmission) -> Option<Self> {
+        let kind = match submission.status {
+            SubmissionStatus::Approved => NotificationKind::SubmissionApproved,
+            SubmissionStatus::Rejected => NotificationKind::SubmissionRejected,
+            SubmissionStatus::ChangesRequested => NotificationKind::SubmissionChangesRequested,
+            SubmissionStatus::Pending => return None,
+        };
+
+        Some(Self {
+            id: Uuid::new_v4(),
+            user_id: submission.submitter_id,
+            kind,
+            submission_id: Some(submission.id),
+            note: submission.review_note.clone(),
+            created_at: Utc::now(),
+            read_at: None,
+        })
+    }
+}
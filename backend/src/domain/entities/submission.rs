@@ -0,0 +1,173 @@
+use std::str::FromStr;
+
+use uuid::Uuid;
+
+use crate::domain::entities::{chat::ChatCategory, errors::{DomainError, DomainResult}, value_objects::{ChatName, GeoPoint, Location}};
+
+/// Where a proposal is in review. Only pending submissions can be decided;
+/// ones sent back for changes become pending again once revised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SubmissionStatus {
+    #[default]
+    Pending,
+    ChangesRequested,
+    Approved,
+    Rejected,
+}
+
+impl SubmissionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmissionStatus::Pending => "pending",
+            SubmissionStatus::ChangesRequested => "changes_requested",
+            SubmissionStatus::Approved => "approved",
+            SubmissionStatus::Rejected => "rejected",
+        }
+    }
+}
+
+impl FromStr for SubmissionStatus {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> DomainResult<Self> {
+        match s {
+            "pending" => Ok(SubmissionStatus::Pending),
+            "changes_requested" => Ok(SubmissionStatus::ChangesRequested),
+            "approved" => Ok(SubmissionStatus::Approved),
+            "rejected" => Ok(SubmissionStatus::Rejected),
+            other => Err(DomainError::UnknownSubmissionStatus(other.to_string())),
+        }
+    }
+}
+
+/// The chat a resident asks to have listed.
+#[derive(Debug, Clone)]
+pub struct ChatProposal {
+    pub name: ChatName,
+    pub description: String,
+    pub location: Location,
+    pub position: Option<GeoPoint>,
+    pub location_id: Option<Uuid>,
+    pub category: ChatCategory,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatSubmission {
+    pub id: Uuid,
+    pub submitter_id: Uuid,
+    pub proposal: ChatProposal,
+    pub status: SubmissionStatus,
+    /// Why it was rejected or what should change
+    pub review_note: Option<String>,
+    /// The listed chat, once approved
+    pub chat_id: Option<Uuid>,
+}
+
+impl ChatSubmission {
+    pub fn new(submitter_id: Uuid, proposal: ChatProposal) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            submitter_id,
+            proposal,
+            status: SubmissionStatus::Pending,
+            review_note: None,
+            chat_id: None,
+        }
+    }
+
+    pub fn with_id(self, id: Uuid) -> Self {
+        Self { id, ..self }
+    }
+
+    pub fn with_review(self, status: SubmissionStatus, review_note: Option<String>, chat_id: Option<Uuid>) -> Self {
+        Self { status, review_note, chat_id, ..self }
+    }
+
+    fn check_pending(&self) -> DomainResult<()> {
+        if self.status != SubmissionStatus::Pending {
+            return Err(DomainError::SubmissionAlreadyDecided);
+        }
+
+        Ok(())
+    }
+
+    pub fn approve(&mut self, chat_id: Uuid) -> DomainResult<()> {
+        self.check_pending()?;
+
+        self.status = SubmissionStatus::Approved;
+        self.review_note = None;
+        self.chat_id = Some(chat_id);
+
+        Ok(())
+    }
+
+    pub fn reject(&mut self, reason: String) -> DomainResult<()> {
+        self.check_pending()?;
+
+        self.status = SubmissionStatus::Rejected;
+        self.review_note = Some(reason);
+
+        Ok(())
+    }
+
+    pub fn request_changes(&mut self, note: String) -> DomainResult<()> {
+        self.check_pending()?;
+
+        self.status = SubmissionStatus::ChangesRequested;
+        self.review_note = Some(note);
+
+        Ok(())
+    }
+
+    /// Only the submitter may revise, and only while nothing is decided.
+    pub fn revise(&mut self, by: Uuid, proposal: ChatProposal) -> DomainResult<()> {
+        if by != self.submitter_id {
+            return Err(DomainError::OperationNotPermitted);
+        }
+
+        if !matches!(self.status, SubmissionStatus::Pending | SubmissionStatus::ChangesRequested) {
+            return Err(DomainError::SubmissionAlreadyDecided);
+        }
+
+        self.proposal = proposal;
+        self.status = SubmissionStatus::Pending;
+        self.review_note = None;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission() -> ChatSubmission {
+        ChatSubmission::new(
+            Uuid::new_v4(),
+            ChatProposal {
+                name: ChatName::parse("Building 5").unwrap(),
+                description: String::new(),
+                location: Location::parse("Main st. 5").unwrap(),
+                position: None,
+                location_id: None,
+                category: ChatCategory::Neighbours,
+            },
+        )
+    }
+
+    #[test]
+    fn test_changes_requested_goes_back_to_pending_on_revision() {
+        let mut submission = submission();
+        let proposal = submission.proposal.clone();
+
+        submission.request_changes("add the entrance number".to_string()).unwrap();
+        assert!(matches!(submission.approve(Uuid::new_v4()), Err(DomainError::SubmissionAlreadyDecided)));
+
+        assert!(matches!(submission.revise(Uuid::new_v4(), proposal.clone()), Err(DomainError::OperationNotPermitted)));
+        submission.revise(submission.submitter_id, proposal.clone()).unwrap();
+        assert_eq!(submission.status, SubmissionStatus::Pending);
+
+        submission.reject("duplicate".to_string()).unwrap();
+        assert!(matches!(submission.revise(submission.submitter_id, proposal), Err(DomainError::SubmissionAlreadyDecided)));
+    }
+}
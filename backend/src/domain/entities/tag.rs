@@ -0,0 +1,16 @@
+use uuid::Uuid;
+
+use crate::domain::entities::value_objects::TagName;
+
+/// Entry of the admin-managed tag vocabulary.
+#[derive(Debug, Clone)]
+pub struct Tag {
+    pub id: Uuid,
+    pub name: TagName,
+}
+
+impl Tag {
+    pub fn new(id: Option<Uuid>, name: TagName) -> Self {
+        Self { id: id.unwrap_or_else(Uuid::new_v4), name }
+    }
+}
@@ -1,17 +1,102 @@
+use std::str::FromStr;
+
 use secrecy::SecretString;
 use uuid::Uuid;
 
-use crate::domain::entities::{chat::{Chat}, errors::{DomainError, DomainResult}};
+use crate::domain::entities::{errors::{DomainError, DomainResult}, submission::{ChatProposal, ChatSubmission}, value_objects::{DisplayName, Email}};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UserRole {
     Admin,
     User,
 }
+
+impl UserRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::Admin => "admin",
+            UserRole::User => "user",
+        }
+    }
+}
+
+impl FromStr for UserRole {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> DomainResult<Self> {
+        match s {
+            "admin" => Ok(UserRole::Admin),
+            "user" => Ok(UserRole::User),
+            other => Err(DomainError::UnknownRole(other.to_string())),
+        }
+    }
+}
+
+/// Suspended accounts keep their data but are hidden from public listings;
+/// closed accounts were anonymised at their owner's request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserStatus {
+    Active,
+    Suspended,
+    Closed,
+}
+
+impl UserStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserStatus::Active => "active",
+            UserStatus::Suspended => "suspended",
+            UserStatus::Closed => "closed",
+        }
+    }
+}
+
+impl FromStr for UserStatus {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> DomainResult<Self> {
+        match s {
+            "active" => Ok(UserStatus::Active),
+            "suspended" => Ok(UserStatus::Suspended),
+            "closed" => Ok(UserStatus::Closed),
+            other => Err(DomainError::UnknownStatus(other.to_string())),
+        }
+    }
+}
+
+/// Which optional profile fields other (non-admin) users may see.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrivacySettings {
+    pub show_email: bool,
+    pub show_role: bool,
+}
+
+impl Default for PrivacySettings {
+    fn default() -> Self {
+        Self { show_email: false, show_role: true }
+    }
+}
+
+/// Whoever performs an operation, for permission checks.
+#[derive(Debug, Clone, Copy)]
+pub struct Actor {
+    pub user_id: Uuid,
+    pub role: UserRole,
+}
+
+impl Actor {
+    pub fn is_admin(&self) -> bool {
+        self.role == UserRole::Admin
+    }
+}
+
 pub struct User {
     id: Uuid,
     role: UserRole,
-    name: String,
-    email: String,
+    status: UserStatus,
+    privacy: PrivacySettings,
+    name: DisplayName,
+    email: Email,
     password_hash: String,
     token_hash: String,
 }
@@ -19,43 +104,50 @@ pub struct User {
 impl User {
     pub fn new(
         id: Uuid, 
-        name: String,
-         email: String,
-         role: String,
+        name: DisplayName,
+         email: Email,
+         role: UserRole,
+         status: UserStatus,
          password_hash: String, 
          token_hash: String //FIXME: 
     ) -> Self {
-        let role = match role.as_str() {
-            "admin" => UserRole::Admin,
-            _ => UserRole::User,
-
-        };
+        Self {id, role, status, privacy: PrivacySettings::default(), name, email, password_hash, token_hash }
+    }
 
-        Self {id, role, name, email, password_hash, token_hash }
+    pub fn with_privacy(self, privacy: PrivacySettings) -> Self {
+        Self { privacy, ..self }
     }
 
     pub fn get_id(&self) -> &Uuid {
-        return &self.id;
+        &self.id
     }
 
     pub fn get_name(&self) -> &str {
-        return &self.name;
+        self.name.as_str()
     }
 
     pub fn get_email(&self) -> &str {
-        return &self.email;
+        self.email.as_str()
     }
 
     pub fn get_role(&self) -> &UserRole {
         &self.role
     }
 
+    pub fn get_status(&self) -> &UserStatus {
+        &self.status
+    }
+
+    pub fn get_privacy(&self) -> &PrivacySettings {
+        &self.privacy
+    }
+
     pub fn get_password_hash(&self) -> SecretString {
-        return SecretString::from(self.password_hash.clone());
+        SecretString::from(self.password_hash.clone())
     }
 
     pub fn get_token_hash(&self) -> SecretString {
-        return SecretString::from(self.token_hash.clone());
+        SecretString::from(self.token_hash.clone())
     }
 
     pub fn change_role_to_admin(&mut self) {
@@ -66,25 +158,13 @@ impl User {
         self.role = UserRole::User;
     }
 
-    pub fn add_chat(
-        &self, 
-        name: String, 
-        description: String,
-        users_count: u64,
-        location: String,
-    ) -> DomainResult<Chat> {
-        if let UserRole::User = self.role {
+    /// Any active account may propose a chat; admins review it before it
+    /// is listed.
+    pub fn submit_chat(&self, proposal: ChatProposal) -> DomainResult<ChatSubmission> {
+        if self.status != UserStatus::Active {
             return Err(DomainError::OperationNotPermitted);
         }
 
-        Ok(Chat::new(None, name, description, users_count, location))
-    }
-
-    pub fn validate_email(&self) -> DomainResult<()>{
-        if !self.email.contains('@') {
-            return Err(DomainError::EmailValidationFailed);
-        }
-
-        Ok(())
+        Ok(ChatSubmission::new(self.id, proposal))
     }
 }
@@ -0,0 +1,429 @@
+use std::fmt::Display;
+
+use crate::domain::entities::errors::{DomainError, DomainResult};
+
+fn trimmed_within(value: &str, max_len: usize) -> Option<String> {
+    let value = value.trim();
+
+    if value.is_empty() || value.chars().count() > max_len {
+        return None;
+    }
+
+    Some(value.to_string())
+}
+
+/// Trimmed, lower-cased address with a local part and a dotted domain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Email(String);
+
+impl Email {
+    pub const MAX_LEN: usize = 254;
+
+    pub fn parse(value: &str) -> DomainResult<Self> {
+        let email = Self::normalize(value);
+
+        let well_formed = email.split_once('@').is_some_and(|(local, domain)| {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+        });
+
+        if !well_formed || email.chars().count() > Self::MAX_LEN || email.contains(char::is_whitespace) {
+            return Err(DomainError::EmailValidationFailed);
+        }
+
+        Ok(Self(email))
+    }
+
+    /// Emails compare case-insensitively, so they are stored trimmed and lower-cased.
+    pub fn normalize(value: &str) -> String {
+        value.trim().to_lowercase()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayName(String);
+
+impl DisplayName {
+    pub const MAX_LEN: usize = 64;
+
+    pub fn parse(value: &str) -> DomainResult<Self> {
+        trimmed_within(value, Self::MAX_LEN)
+            .map(Self)
+            .ok_or(DomainError::DisplayNameInvalid)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatName(String);
+
+impl ChatName {
+    pub const MAX_LEN: usize = 128;
+
+    pub fn parse(value: &str) -> DomainResult<Self> {
+        trimmed_within(value, Self::MAX_LEN)
+            .map(Self)
+            .ok_or(DomainError::ChatNameInvalid)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location(String);
+
+impl Location {
+    pub const MAX_LEN: usize = 256;
+
+    pub fn parse(value: &str) -> DomainResult<Self> {
+        trimmed_within(value, Self::MAX_LEN)
+            .map(Self)
+            .ok_or(DomainError::LocationInvalid)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Name of a node in the location tree, e.g. a district or street.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceName(String);
+
+impl PlaceName {
+    pub const MAX_LEN: usize = 128;
+
+    pub fn parse(value: &str) -> DomainResult<Self> {
+        trimmed_within(value, Self::MAX_LEN)
+            .map(Self)
+            .ok_or(DomainError::PlaceNameInvalid)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Entry of the tag vocabulary, e.g. `school 12`. Stored lower-cased so
+/// spellings cannot diverge; commas separate tags in query strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagName(String);
+
+impl TagName {
+    pub const MAX_LEN: usize = 48;
+
+    pub fn parse(value: &str) -> DomainResult<Self> {
+        trimmed_within(value, Self::MAX_LEN)
+            .filter(|v| !v.contains(','))
+            .map(|v| Self(v.to_lowercase()))
+            .ok_or(DomainError::TagNameInvalid)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// `https` address of the messenger group behind a chat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InviteLink(String);
+
+impl InviteLink {
+    pub const MAX_LEN: usize = 512;
+
+    pub fn parse(value: &str) -> DomainResult<Self> {
+        let value = value.trim();
+
+        let host_ok = value
+            .strip_prefix("https://")
+            .map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default())
+            .is_some_and(|host| host.contains('.') && !host.starts_with('.') && !host.contains('@'));
+
+        if !host_ok || value.chars().count() > Self::MAX_LEN || value.contains(char::is_whitespace) {
+            return Err(DomainError::InviteLinkInvalid);
+        }
+
+        Ok(Self(value.to_string()))
+    }
+
+    /// Lower-cased host, without any port.
+    pub fn host(&self) -> String {
+        let rest = &self.0["https://".len()..];
+        let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+
+        host.split(':').next().unwrap_or_default().to_lowercase()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Messenger hosts invite links may point to, each including its subdomains.
+#[derive(Debug, Clone)]
+pub struct LinkAllowList(Vec<String>);
+
+impl LinkAllowList {
+    pub const DEFAULT_HOSTS: [&str; 7] = [
+        "t.me",
+        "telegram.me",
+        "chat.whatsapp.com",
+        "vk.me",
+        "vk.com",
+        "max.ru",
+        "discord.gg",
+    ];
+
+    pub fn new<I, S>(hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let hosts = hosts
+            .into_iter()
+            .map(|h| h.as_ref().trim().trim_start_matches('.').to_lowercase())
+            .filter(|h| !h.is_empty())
+            .collect();
+
+        Self(hosts)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn check(&self, link: &InviteLink) -> DomainResult<()> {
+        let host = link.host();
+
+        if !self.allows_host(&host) {
+            return Err(DomainError::InviteLinkHostNotAllowed(host));
+        }
+
+        Ok(())
+    }
+
+    /// `host` is expected lower-cased and without a port.
+    pub fn allows_host(&self, host: &str) -> bool {
+        self.0.iter().any(|allowed| {
+            host == allowed || host.strip_suffix(allowed.as_str()).is_some_and(|sub| sub.ends_with('.'))
+        })
+    }
+}
+
+impl Default for LinkAllowList {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_HOSTS)
+    }
+}
+
+/// Non-negative and small enough to round-trip through a Postgres `bigint`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MemberCount(u64);
+
+impl MemberCount {
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        // Constructors keep the value within i64 range
+        self.0 as i64
+    }
+}
+
+impl TryFrom<i64> for MemberCount {
+    type Error = DomainError;
+
+    fn try_from(value: i64) -> DomainResult<Self> {
+        u64::try_from(value)
+            .map(Self)
+            .map_err(|_| DomainError::MemberCountOutOfRange)
+    }
+}
+
+impl TryFrom<u64> for MemberCount {
+    type Error = DomainError;
+
+    fn try_from(value: u64) -> DomainResult<Self> {
+        i64::try_from(value)
+            .map(|_| Self(value))
+            .map_err(|_| DomainError::MemberCountOutOfRange)
+    }
+}
+
+/// WGS84 position in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoPoint {
+    lat: f64,
+    lon: f64,
+}
+
+impl GeoPoint {
+    /// Mean Earth radius in metres, as used by the haversine formula.
+    pub const EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+    pub fn new(lat: f64, lon: f64) -> DomainResult<Self> {
+        if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+            return Err(DomainError::CoordinatesInvalid);
+        }
+
+        Ok(Self { lat, lon })
+    }
+
+    pub fn lat(&self) -> f64 {
+        self.lat
+    }
+
+    pub fn lon(&self) -> f64 {
+        self.lon
+    }
+
+    /// Great-circle distance in metres.
+    pub fn distance_m(&self, other: &GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.lon - self.lon).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+
+        2.0 * Self::EARTH_RADIUS_M * a.sqrt().min(1.0).asin()
+    }
+
+    /// `(min_lat, max_lat, min_lon, max_lon)` enclosing every point within
+    /// `radius_m`; longitude spans the whole range near the poles or when
+    /// the box would cross the antimeridian.
+    pub fn bounding_box(&self, radius_m: f64) -> (f64, f64, f64, f64) {
+        let d_lat = (radius_m / Self::EARTH_RADIUS_M).to_degrees();
+
+        let min_lat = (self.lat - d_lat).max(-90.0);
+        let max_lat = (self.lat + d_lat).min(90.0);
+
+        if min_lat <= -90.0 || max_lat >= 90.0 {
+            return (min_lat, max_lat, -180.0, 180.0);
+        }
+
+        let d_lon = (radius_m / (Self::EARTH_RADIUS_M * self.lat.to_radians().cos())).to_degrees();
+
+        if self.lon - d_lon < -180.0 || self.lon + d_lon > 180.0 {
+            return (min_lat, max_lat, -180.0, 180.0);
+        }
+
+        (min_lat, max_lat, self.lon - d_lon, self.lon + d_lon)
+    }
+}
+
+macro_rules! impl_display {
+    ($($t:ty),*) => {
+        $(impl Display for $t {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        })*
+    };
+}
+
+impl_display!(Email, DisplayName, ChatName, Location, PlaceName, TagName, MemberCount);
+
+macro_rules! impl_from_stored {
+    ($($t:ty),*) => {
+        $(impl $t {
+            /// Wraps a stored value as is. Rows written before input was
+            /// validated may not pass `parse`, but must still load.
+            pub fn from_stored(value: String) -> Self {
+                Self(value)
+            }
+        })*
+    };
+}
+
+impl_from_stored!(Email, DisplayName, ChatName, Location);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_is_normalised_and_checked() {
+        assert_eq!(Email::parse("  Anna@Example.ORG ").unwrap().as_str(), "anna@example.org");
+
+        for bad in ["", "anna", "@example.org", "anna@example", "anna@.org", "an na@example.org", "a@b@c.org"] {
+            assert!(Email::parse(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_names_are_trimmed_and_bounded() {
+        assert_eq!(DisplayName::parse(" Anna ").unwrap().as_str(), "Anna");
+        assert!(DisplayName::parse("   ").is_err());
+        assert!(ChatName::parse(&"x".repeat(ChatName::MAX_LEN + 1)).is_err());
+        assert!(Location::parse(&"x".repeat(Location::MAX_LEN)).is_ok());
+        assert_eq!(TagName::parse(" Dog Walkers ").unwrap().as_str(), "dog walkers");
+        assert!(TagName::parse("dogs,cats").is_err());
+    }
+
+    #[test]
+    fn test_stored_values_load_unchecked() {
+        assert!(Email::parse("admin@localhost").is_err());
+        assert_eq!(Email::from_stored("admin@localhost".to_string()).as_str(), "admin@localhost");
+        assert_eq!(DisplayName::from_stored(String::new()).as_str(), "");
+    }
+
+    #[test]
+    fn test_invite_link_needs_https_host() {
+        assert_eq!(InviteLink::parse(" https://T.me:443/joinchat/abc ").unwrap().host(), "t.me");
+
+        for bad in ["", "http://t.me/abc", "https://", "https://localhost/x", "https://user@t.me/x", "https://t.me/a b"] {
+            assert!(InviteLink::parse(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_link_allow_list_matches_hosts_and_subdomains_only() {
+        let allow_list = LinkAllowList::new([" T.me ", ".chat.whatsapp.com", ""]);
+        let check = |link: &str| allow_list.check(&InviteLink::parse(link).unwrap()).is_ok();
+
+        assert!(check("https://t.me/joinchat/abc"));
+        assert!(check("https://web.t.me/abc"));
+        assert!(check("https://chat.whatsapp.com/abc"));
+        assert!(!check("https://evilt.me/abc"));
+        assert!(!check("https://t.me.example.org/abc"));
+        assert!(!check("https://vk.me/abc"));
+        assert!(!allow_list.allows_host("169.254.169.254"));
+    }
+
+    #[test]
+    fn test_member_count_range() {
+        assert_eq!(MemberCount::try_from(5i64).unwrap().get(), 5);
+        assert!(MemberCount::try_from(-1i64).is_err());
+        assert!(MemberCount::try_from(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_geo_point_distance_and_box() {
+        assert!(GeoPoint::new(91.0, 0.0).is_err());
+        assert!(GeoPoint::new(0.0, -180.5).is_err());
+
+        // Moscow, Red Square to Saint Petersburg, Palace Square: about 634 km
+        let moscow = GeoPoint::new(55.7539, 37.6208).unwrap();
+        let spb = GeoPoint::new(59.9390, 30.3158).unwrap();
+
+        assert!((moscow.distance_m(&spb) - 634_000.0).abs() < 5_000.0);
+
+        let (min_lat, max_lat, min_lon, max_lon) = moscow.bounding_box(1_000.0);
+
+        assert!(min_lat < moscow.lat() && moscow.lat() < max_lat);
+        assert!(min_lon < moscow.lon() && moscow.lon() < max_lon);
+    }
+}
@@ -1,31 +1,83 @@
 mod app;
 mod config;
+mod cors;
 mod db;
+mod frontend;
+mod link_check;
+mod purge;
+mod shutdown;
+mod tls;
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use crate::{
-    adapters::{api::app_state::AppState, crypto::argon::ArgonHasher, db::postgres::{PostgresChatRepo, PostgresUserRepo}},
-    application::use_cases::UseCases
+    adapters::{api::app_state::AppState, crypto::argon::ArgonHasher, db::postgres::{PostgresChatRepo, PostgresFavoriteRepo, PostgresLinkCheckRepo, PostgresLocationRepo, PostgresNotificationRepo, PostgresSubmissionRepo, PostgresTagRepo, PostgresUserRepo}, http::prober::HttpLinkProber},
+    application::{link_check::LinkMonitor, use_cases::UseCases}
 };
 
+/// Between two invite link requests of the dead-link checker.
+const LINK_CHECK_PAUSE: Duration = Duration::from_secs(1);
+
 pub async fn init_app() -> anyhow::Result<()> {
     let server = app::Server::new("my_app".to_string())?;
 
     let db_pool = db::init_postgres_db(&server.config.database_url).await?;
 
     let postgres_user_repo = PostgresUserRepo::new(db_pool.clone());
-    let postgres_chat_repo = PostgresChatRepo::new(db_pool);
+    let postgres_chat_repo = PostgresChatRepo::new(db_pool.clone());
+    let postgres_location_repo = PostgresLocationRepo::new(db_pool.clone());
+    let postgres_tag_repo = PostgresTagRepo::new(db_pool.clone());
+    let postgres_submission_repo = PostgresSubmissionRepo::new(db_pool.clone());
+    let postgres_notification_repo = PostgresNotificationRepo::new(db_pool.clone());
+    let postgres_favorite_repo = PostgresFavoriteRepo::new(db_pool.clone());
 
     let argon_hasher = ArgonHasher::new();
 
-    let use_cases = UseCases::new(Arc::new(postgres_user_repo), Arc::new(postgres_chat_repo), Arc::new(argon_hasher));
+    let use_cases = UseCases::new(
+        Arc::new(postgres_user_repo),
+        Arc::new(postgres_chat_repo),
+        Arc::new(postgres_location_repo),
+        Arc::new(postgres_tag_repo),
+        Arc::new(postgres_submission_repo),
+        Arc::new(postgres_notification_repo),
+        Arc::new(postgres_favorite_repo),
+        Arc::new(argon_hasher),
+    )
+    .with_account_deletion(server.config.account_deletion()?)
+    .with_retention(server.config.retention()?)
+    .with_link_allow_list(server.config.link_allow_list());
+
+    let use_cases = Arc::new(use_cases);
+
+    purge::spawn_purge_job(use_cases.clone(), server.config.purge_interval(), server.shutdown.subscribe());
+
+    let link_monitor = LinkMonitor::new(
+        Arc::new(PostgresLinkCheckRepo::new(db_pool.clone())),
+        Arc::new(HttpLinkProber::new(server.config.link_check_timeout(), server.config.link_allow_list())?),
+    )
+    .with_failure_threshold(server.config.link_check_failure_threshold)
+    .with_pause(LINK_CHECK_PAUSE);
+
+    let link_monitor = Arc::new(link_monitor);
+
+    if let Some(every) = server.config.link_check_interval() {
+        link_check::spawn_link_check_job(link_monitor.clone(), every, server.shutdown.subscribe());
+    }
 
     let app_state = AppState {
-        use_cases: Arc::new(use_cases),
+        use_cases,
+        link_monitor,
+        cookies: server.config.cookie_settings()?,
+        trusted_origins: Arc::new(server.trusted_origins()),
+        hide_invite_links: server.config.hide_invite_links,
     };
 
-    server.start(app_state).await?;
+    let result = server.start(app_state).await;
+
+    println!("->> {:<12} - closing database pool", "SHUTDOWN");
+    db_pool.close().await;
+
+    result?;
 
     anyhow::Ok(())
 }
@@ -1,37 +1,149 @@
-use axum::{Router, middleware, routing::post, serve};
+use std::{future::IntoFuture, net::SocketAddr, time::Duration};
+
+use anyhow::Context;
+use axum::{Router, middleware, serve};
+use axum_server::Handle;
 use tower_cookies::CookieManagerLayer;
 
-use crate::adapters::api::{app_state::AppState, chat::chat_controller::chat_router, login::login_controller, middlewares, user::user_controller::user_router};
+use crate::{
+    adapters::api::{
+        app_state::AppState,
+        middlewares,
+        openapi::{api_routes, docs_router},
+    },
+    infrastructure::{
+        config::TlsConfig,
+        cors::cors_layer,
+        frontend::frontend_router,
+        shutdown::{Shutdown, shutdown_signal},
+        tls,
+    },
+};
 
 pub struct Server {
     pub app_name: String,
     pub config: super::config::Config,
+    pub shutdown: Shutdown,
 }
 
 impl Server {
     pub fn new(app_name: String) -> anyhow::Result<Self> {
         let app_config = super::config::init_config()?;
-        anyhow::Ok(Self { app_name, config: app_config })
+        anyhow::Ok(Self { app_name, config: app_config, shutdown: Shutdown::new() })
     }
 
     pub async fn start(&self, app_state: AppState) -> anyhow::Result<()> {
         println!("Start {}", self.app_name);
 
 
-        let router: Router = Router::new()
-            .merge(user_router())
-            .merge(chat_router())
-            .route("/api/login", post(login_controller::login))
-            .route("/api/logout", post(login_controller::logout))
+        let (api_router, openapi) = api_routes().split_for_parts();
+
+        let mut router: Router = api_router
+            .layer(middleware::from_fn_with_state(app_state.clone(), middlewares::csrf_protection))
             .layer(middleware::map_response(middlewares::main_response_middleware))
             .layer(middleware::from_fn_with_state(app_state.clone(), middlewares::context_resolver))
             .layer(CookieManagerLayer::new())
-            .with_state(app_state);
+            .layer(cors_layer(&self.config)?)
+            .with_state(app_state)
+            .merge(docs_router(openapi, self.config.api_docs_ui()));
+
+        if let Some(frontend) = frontend_router(&self.config) {
+            router = router.merge(frontend);
+        }
+
+        let addr = SocketAddr::from(([0, 0, 0, 0], self.config.port));
+
+        match self.config.tls()? {
+            Some(tls_config) => self.serve_tls(router, addr, tls_config).await,
+            None => self.serve_plain(router, addr).await,
+        }
+    }
+
+    /// Origins the CSRF check accepts: explicitly trusted ones plus every CORS origin.
+    pub fn trusted_origins(&self) -> Vec<String> {
+        let mut origins = self.config.trusted_origins.clone();
+
+        origins.extend(self.config.cors_origins());
+        origins.sort();
+        origins.dedup();
+
+        origins
+    }
+
+    fn drain_timeout(&self) -> Duration {
+        Duration::from_secs(self.config.shutdown_timeout_secs)
+    }
+
+    async fn serve_plain(&self, router: Router, addr: SocketAddr) -> anyhow::Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("failed to bind listener on {addr}"))?;
+
+        let mut draining = self.shutdown.subscribe();
+
+        let server = serve(listener, router)
+            .with_graceful_shutdown(async move {
+                let _ = draining.wait_for(|d| *d).await;
+            })
+            .into_future();
+
+        tokio::pin!(server);
 
-        let listener = tokio::net::TcpListener::bind("0.0.0.0:8000").await.unwrap();
+        tokio::select! {
+            result = &mut server => result?,
+            _ = shutdown_signal() => {
+                self.shutdown.notify();
 
-        serve(listener, router).await?;
+                let drain_timeout = self.drain_timeout();
+
+                match tokio::time::timeout(drain_timeout, &mut server).await {
+                    Ok(result) => result?,
+                    Err(_) => println!(
+                        "->> {:<12} - drain timeout of {drain_timeout:?} elapsed, dropping remaining connections",
+                        "SHUTDOWN"
+                    ),
+                }
+            }
+        }
 
         anyhow::Ok(())
     }
-}
\ No newline at end of file
+
+    async fn serve_tls(&self, router: Router, addr: SocketAddr, tls_config: TlsConfig) -> anyhow::Result<()> {
+        let rustls_config = tls::load_rustls_config(&tls_config).await?;
+
+        tls::spawn_cert_reloader(rustls_config.clone(), tls_config.clone(), self.shutdown.subscribe());
+
+        if let Some(redirect_port) = tls_config.redirect_port {
+            let https_port = self.config.port;
+            let draining = self.shutdown.subscribe();
+
+            tokio::spawn(async move {
+                if let Err(e) = tls::serve_https_redirect(redirect_port, https_port, draining).await {
+                    println!("->> {:<12} - redirect listener stopped: {e:#}", "TLS");
+                }
+            });
+        }
+
+        let handle = Handle::new();
+
+        let server = axum_server::bind_rustls(addr, rustls_config)
+            .handle(handle.clone())
+            .serve(router.into_make_service());
+
+        tokio::pin!(server);
+
+        tokio::select! {
+            result = &mut server => result.with_context(|| format!("HTTPS listener on {addr} failed"))?,
+            _ = shutdown_signal() => {
+                self.shutdown.notify();
+
+                handle.graceful_shutdown(Some(self.drain_timeout()));
+
+                server.await?;
+            }
+        }
+
+        anyhow::Ok(())
+    }
+}
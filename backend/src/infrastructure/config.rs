@@ -1,9 +1,263 @@
-use anyhow::Ok;
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::{Ok, bail};
+use chrono::TimeDelta;
 use serde::Deserialize;
+use tower_cookies::cookie::SameSite;
+
+use crate::{adapters::api::cookies::CookieSettings, application::{link_check::DEFAULT_FAILURE_THRESHOLD, retention::{AccountDeletion, DEFAULT_RETENTION_DAYS}}, domain::entities::value_objects::LinkAllowList};
 
 #[derive(Deserialize, Debug)]
 pub struct Config {
     pub database_url: String,
+
+    /// `development` or `production`; selects presets such as CORS origins
+    #[serde(default = "default_app_env")]
+    pub app_env: String,
+
+    #[serde(default = "default_port")]
+    pub port: u16,
+
+    /// Seconds to wait for in-flight requests after a shutdown signal
+    #[serde(default = "default_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64,
+
+    // TLS (served natively only when both paths are set)
+    pub tls_cert_path: Option<PathBuf>,
+    pub tls_key_path: Option<PathBuf>,
+
+    /// Plain HTTP port answering with redirects to the HTTPS listener
+    pub tls_redirect_port: Option<u16>,
+
+    #[serde(default = "default_tls_reload_interval_secs")]
+    pub tls_reload_interval_secs: u64,
+
+    // Cookies
+    /// `strict`, `lax` or `none`
+    #[serde(default = "default_cookie_same_site")]
+    pub cookie_same_site: String,
+
+    /// Defaults to on when TLS is terminated by the backend
+    pub cookie_secure: Option<bool>,
+
+    pub cookie_domain: Option<String>,
+
+    /// Comma-separated origins allowed to send state-changing requests
+    #[serde(default)]
+    pub trusted_origins: Vec<String>,
+
+    // CORS
+    /// Comma-separated origins allowed to call the API from the browser
+    #[serde(default)]
+    pub cors_allowed_origins: Vec<String>,
+
+    /// How long browsers may cache a preflight response
+    #[serde(default = "default_cors_max_age_secs")]
+    pub cors_max_age_secs: u64,
+
+    /// Built frontend (`frontend/dist`) to serve next to the API; takes
+    /// precedence over assets embedded with the `embed-frontend` feature
+    pub frontend_dir: Option<PathBuf>,
+
+    /// Interactive API reference at `/api/docs`; defaults to on in development
+    pub api_docs_ui: Option<bool>,
+
+    /// `anonymise` or `erase`, applied when users close their own account
+    #[serde(default = "default_account_deletion")]
+    pub account_deletion: String,
+
+    /// Days deleted users and chats stay restorable before being purged
+    #[serde(default = "default_retention_days")]
+    pub retention_days: i64,
+
+    /// How often the purge job looks for expired tombstones
+    #[serde(default = "default_purge_interval_secs")]
+    pub purge_interval_secs: u64,
+
+    // Invite links
+    /// Comma-separated messenger hosts invite links may point to; a built-in
+    /// list of common messengers if unset
+    #[serde(default)]
+    pub invite_link_hosts: Vec<String>,
+
+    /// Withhold raw invite links from everyone but chat owners and admins, and
+    /// let only signed-in users follow `/c/{id}`
+    #[serde(default)]
+    pub hide_invite_links: bool,
+
+    /// How often every invite link is requested; `0` turns the checker off
+    #[serde(default = "default_link_check_interval_secs")]
+    pub link_check_interval_secs: u64,
+
+    /// Failed checks in a row after which a link is reported as dead
+    #[serde(default = "default_link_check_failure_threshold")]
+    pub link_check_failure_threshold: u32,
+
+    #[serde(default = "default_link_check_timeout_secs")]
+    pub link_check_timeout_secs: u64,
+}
+
+/// Frontend dev servers (docker-compose nginx and `vite dev`).
+const DEVELOPMENT_CORS_ORIGINS: [&str; 4] = [
+    "http://localhost:3000",
+    "http://127.0.0.1:3000",
+    "http://localhost:5173",
+    "http://127.0.0.1:5173",
+];
+
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+    pub redirect_port: Option<u16>,
+    pub reload_interval: Duration,
+}
+
+impl Config {
+    pub fn tls(&self) -> anyhow::Result<Option<TlsConfig>> {
+        match (&self.tls_cert_path, &self.tls_key_path) {
+            (Some(cert_path), Some(key_path)) => Ok(Some(TlsConfig {
+                cert_path: cert_path.clone(),
+                key_path: key_path.clone(),
+                redirect_port: self.tls_redirect_port,
+                reload_interval: Duration::from_secs(self.tls_reload_interval_secs),
+            })),
+            (None, None) => Ok(None),
+            _ => bail!("TLS_CERT_PATH and TLS_KEY_PATH must be set together"),
+        }
+    }
+
+    pub fn is_development(&self) -> bool {
+        self.app_env.eq_ignore_ascii_case("development")
+    }
+
+    pub fn api_docs_ui(&self) -> bool {
+        self.api_docs_ui.unwrap_or(self.is_development())
+    }
+
+    pub fn cors_origins(&self) -> Vec<String> {
+        let mut origins: Vec<String> = self
+            .cors_allowed_origins
+            .iter()
+            .map(|o| o.trim().trim_end_matches('/').to_string())
+            .filter(|o| !o.is_empty())
+            .collect();
+
+        if self.is_development() {
+            origins.extend(DEVELOPMENT_CORS_ORIGINS.iter().map(|o| o.to_string()));
+        }
+
+        origins.sort();
+        origins.dedup();
+
+        origins
+    }
+
+    pub fn account_deletion(&self) -> anyhow::Result<AccountDeletion> {
+        self.account_deletion
+            .parse()
+            .map_err(|e| anyhow::anyhow!("ACCOUNT_DELETION: {e}"))
+    }
+
+    pub fn retention(&self) -> anyhow::Result<TimeDelta> {
+        if self.retention_days < 0 {
+            bail!("RETENTION_DAYS must not be negative");
+        }
+
+        Ok(TimeDelta::days(self.retention_days))
+    }
+
+    pub fn link_allow_list(&self) -> LinkAllowList {
+        let allow_list = LinkAllowList::new(&self.invite_link_hosts);
+
+        if allow_list.is_empty() {
+            return LinkAllowList::default();
+        }
+
+        allow_list
+    }
+
+    /// `None` when the dead-link checker is turned off.
+    pub fn link_check_interval(&self) -> Option<Duration> {
+        (self.link_check_interval_secs > 0).then(|| Duration::from_secs(self.link_check_interval_secs))
+    }
+
+    pub fn link_check_timeout(&self) -> Duration {
+        Duration::from_secs(self.link_check_timeout_secs.max(1))
+    }
+
+    pub fn purge_interval(&self) -> Duration {
+        Duration::from_secs(self.purge_interval_secs.max(1))
+    }
+
+    pub fn cookie_settings(&self) -> anyhow::Result<CookieSettings> {
+        let same_site = match self.cookie_same_site.to_lowercase().as_str() {
+            "strict" => SameSite::Strict,
+            "lax" => SameSite::Lax,
+            "none" => SameSite::None,
+            other => bail!("COOKIE_SAME_SITE must be strict, lax or none, got {other:?}"),
+        };
+
+        let secure = self.cookie_secure.unwrap_or(self.tls()?.is_some());
+
+        if same_site == SameSite::None && !secure {
+            bail!("COOKIE_SAME_SITE=none requires secure cookies");
+        }
+
+        Ok(CookieSettings {
+            secure,
+            same_site,
+            domain: self.cookie_domain.clone(),
+        })
+    }
+}
+
+fn default_app_env() -> String {
+    "production".to_string()
+}
+
+fn default_port() -> u16 {
+    8000
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+    30
+}
+
+fn default_tls_reload_interval_secs() -> u64 {
+    60
+}
+
+fn default_cookie_same_site() -> String {
+    "lax".to_string()
+}
+
+fn default_account_deletion() -> String {
+    "anonymise".to_string()
+}
+
+fn default_retention_days() -> i64 {
+    DEFAULT_RETENTION_DAYS
+}
+
+fn default_purge_interval_secs() -> u64 {
+    3600
+}
+
+fn default_link_check_interval_secs() -> u64 {
+    6 * 3600
+}
+
+fn default_link_check_failure_threshold() -> u32 {
+    DEFAULT_FAILURE_THRESHOLD
+}
+
+fn default_link_check_timeout_secs() -> u64 {
+    10
+}
+
+fn default_cors_max_age_secs() -> u64 {
+    600
 }
 
 pub fn init_config() -> anyhow::Result<Config> {
@@ -11,4 +265,4 @@ pub fn init_config() -> anyhow::Result<Config> {
 
     Ok(config)
 
-}
\ No newline at end of file
+}
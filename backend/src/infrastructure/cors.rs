@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use axum::http::{HeaderName, HeaderValue, Method, header};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::{adapters::api::{CSRF_HEADER, NEXT_CURSOR_HEADER, TOTAL_COUNT_HEADER}, infrastructure::config::Config};
+
+/// Explicit allow-lists only: credentialed requests (the auth cookie) may not
+/// be combined with wildcard origins, methods or headers.
+pub fn cors_layer(config: &Config) -> anyhow::Result<CorsLayer> {
+    let origins = config
+        .cors_origins()
+        .iter()
+        .map(|o| HeaderValue::from_str(o).with_context(|| format!("invalid CORS origin {o:?}")))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    println!("->> {:<12} - allowed origins: {origins:?}", "CORS");
+
+    let layer = CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_credentials(true)
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
+        .allow_headers([header::CONTENT_TYPE, HeaderName::from_static(CSRF_HEADER)])
+        .expose_headers([
+            HeaderName::from_static(TOTAL_COUNT_HEADER),
+            HeaderName::from_static(NEXT_CURSOR_HEADER),
+        ])
+        .max_age(Duration::from_secs(config.cors_max_age_secs));
+
+    Ok(layer)
+}
@@ -6,5 +6,7 @@ pub async fn init_postgres_db(db_url: &str) -> anyhow::Result<PgPool> {
         .connect(db_url)
         .await?;
 
+    sqlx::migrate!("./migrations").run(&pool).await?;
+
     Ok(pool)
-}
\ No newline at end of file
+}
@@ -0,0 +1,154 @@
+use std::path::Path;
+
+use axum::{
+    Router,
+    extract::Request,
+    http::{HeaderValue, StatusCode, header},
+    middleware::{self, Next},
+    response::Response,
+    routing::any,
+};
+use tower_http::services::{ServeDir, ServeFile};
+
+use crate::infrastructure::config::Config;
+
+/// Vite puts content-hashed bundles here, so they can be cached forever.
+const HASHED_ASSETS_PREFIX: &str = "/assets/";
+
+/// Router serving the built Vue app, or `None` when the frontend is shipped
+/// separately. Must be merged after the API routes so `/api` wins.
+pub fn frontend_router(config: &Config) -> Option<Router> {
+    let router = match &config.frontend_dir {
+        Some(dir) => dir_router(dir),
+        None => embedded::router()?,
+    };
+
+    let router = router
+        // Unknown API paths must stay 404s instead of falling through to the SPA
+        .route("/api/{*rest}", any(|| async { StatusCode::NOT_FOUND }))
+        .layer(middleware::from_fn(cache_control));
+
+    Some(router)
+}
+
+fn dir_router(dir: &Path) -> Router {
+    println!("->> {:<12} - serving frontend from {dir:?}", "FRONTEND");
+
+    let index = ServeFile::new(dir.join("index.html"))
+        .precompressed_br()
+        .precompressed_gzip();
+
+    // No SPA fallback for hashed bundles: a stale hash must be a 404, not an
+    // index.html cached for a year
+    let hashed = ServeDir::new(dir.join(HASHED_ASSETS_PREFIX.trim_matches('/')))
+        .precompressed_br()
+        .precompressed_gzip();
+
+    let assets = ServeDir::new(dir)
+        .precompressed_br()
+        .precompressed_gzip()
+        .fallback(index);
+
+    Router::new()
+        .nest_service(HASHED_ASSETS_PREFIX.trim_end_matches('/'), hashed)
+        .fallback_service(assets)
+}
+
+async fn cache_control(req: Request, next: Next) -> Response {
+    let immutable = req.uri().path().starts_with(HASHED_ASSETS_PREFIX);
+
+    let mut res = next.run(req).await;
+
+    if res.status().is_success() {
+        let value = if immutable {
+            "public, max-age=31536000, immutable"
+        } else {
+            // index.html references the current bundle hashes and must be revalidated
+            "no-cache"
+        };
+
+        res.headers_mut()
+            .insert(header::CACHE_CONTROL, HeaderValue::from_static(value));
+    }
+
+    res
+}
+
+#[cfg(feature = "embed-frontend")]
+mod embedded {
+    use axum::{
+        Router,
+        body::Body,
+        http::{HeaderMap, HeaderValue, StatusCode, Uri, header},
+        response::{IntoResponse, Response},
+    };
+    use rust_embed::{EmbeddedFile, RustEmbed};
+
+    #[derive(RustEmbed)]
+    #[folder = "../frontend/dist"]
+    struct Assets;
+
+    pub fn router() -> Option<Router> {
+        println!("->> {:<12} - serving embedded frontend", "FRONTEND");
+
+        Some(Router::new().fallback(serve))
+    }
+
+    async fn serve(uri: Uri, headers: HeaderMap) -> Response {
+        let path = uri.path().trim_start_matches('/');
+
+        let path = if path.is_empty() { "index.html" } else { path };
+
+        match Assets::get(path) {
+            Some(file) => respond(path, file, &headers),
+            None if uri.path().starts_with(super::HASHED_ASSETS_PREFIX) => StatusCode::NOT_FOUND.into_response(),
+            // SPA fallback: client-side routes are resolved by vue-router
+            None => match Assets::get("index.html") {
+                Some(index) => respond("index.html", index, &headers),
+                None => StatusCode::NOT_FOUND.into_response(),
+            },
+        }
+    }
+
+    fn respond(path: &str, file: EmbeddedFile, headers: &HeaderMap) -> Response {
+        let accepted = headers
+            .get(header::ACCEPT_ENCODING)
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or_default();
+
+        let mime = file.metadata.mimetype().to_string();
+
+        // Prefer a precompressed sibling produced at build time, if any.
+        let (body, encoding) = [("br", ".br"), ("gzip", ".gz")]
+            .into_iter()
+            .filter(|(encoding, _)| accepted.contains(encoding))
+            .find_map(|(encoding, ext)| {
+                Assets::get(&format!("{path}{ext}")).map(|f| (f.data, Some(encoding)))
+            })
+            .unwrap_or((file.data, None));
+
+        let mut res = Body::from(body.into_owned()).into_response();
+
+        let res_headers = res.headers_mut();
+
+        if let Ok(mime) = HeaderValue::from_str(&mime) {
+            res_headers.insert(header::CONTENT_TYPE, mime);
+        }
+
+        if let Some(encoding) = encoding {
+            res_headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static(encoding));
+            res_headers.insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+        }
+
+        res
+    }
+}
+
+#[cfg(not(feature = "embed-frontend"))]
+mod embedded {
+    use axum::Router;
+
+    pub fn router() -> Option<Router> {
+        None
+    }
+}
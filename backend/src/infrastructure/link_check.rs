@@ -0,0 +1,32 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::watch;
+
+use crate::application::link_check::{LinkCheckRun, LinkMonitor};
+
+/// Periodically requests every chat's invite link. A pass in progress is
+/// abandoned on shutdown; checks already made are kept.
+pub fn spawn_link_check_job(monitor: Arc<LinkMonitor>, every: Duration, mut shutdown: watch::Receiver<bool>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {},
+                _ = shutdown.wait_for(|d| *d) => break,
+            }
+
+            tokio::select! {
+                result = monitor.check_links() => match result {
+                    Ok(LinkCheckRun { checked: 0, .. }) => {}
+                    Ok(LinkCheckRun { checked, failed, dead }) => {
+                        println!("->> {:<12} - checked {checked} invite links, {failed} failed, {dead} dead", "LINK_CHECK");
+                    }
+                    // Retried on the next tick
+                    Err(e) => println!("->> {:<12} - link check failed: {e:?}", "LINK_CHECK"),
+                },
+                _ = shutdown.wait_for(|d| *d) => break,
+            }
+        }
+    });
+}
@@ -0,0 +1,29 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::watch;
+
+use crate::application::use_cases::UseCases;
+
+/// Periodically hard-deletes users and chats whose tombstones are older than
+/// the retention window. Stops with the server.
+pub fn spawn_purge_job(use_cases: Arc<UseCases>, every: Duration, mut shutdown: watch::Receiver<bool>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(every);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {},
+                _ = shutdown.wait_for(|d| *d) => break,
+            }
+
+            match use_cases.purge_deleted().await {
+                Ok((0, 0)) => {}
+                Ok((users, chats)) => {
+                    println!("->> {:<12} - purged {users} users and {chats} chats", "PURGE");
+                }
+                // Retried on the next tick
+                Err(e) => println!("->> {:<12} - purge failed: {e:?}", "PURGE"),
+            }
+        }
+    });
+}
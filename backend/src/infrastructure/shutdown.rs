@@ -0,0 +1,56 @@
+use tokio::{signal, sync::watch};
+
+/// Fan-out of the shutdown event to everything that outlives a single request:
+/// the listeners and the background jobs.
+pub struct Shutdown {
+    sender: watch::Sender<bool>,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (sender, _) = watch::channel(false);
+
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.sender.subscribe()
+    }
+
+    pub fn notify(&self) {
+        println!("->> {:<12} - notifying {} subscriber(s)", "SHUTDOWN", self.sender.receiver_count());
+
+        self.sender.send_replace(true);
+    }
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resolves on the first SIGINT (Ctrl+C) or SIGTERM.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => println!("->> {:<12} - received SIGINT", "SHUTDOWN"),
+        _ = terminate => println!("->> {:<12} - received SIGTERM", "SHUTDOWN"),
+    }
+}
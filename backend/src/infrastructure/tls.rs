@@ -0,0 +1,159 @@
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use anyhow::Context;
+use axum::{
+    Router,
+    http::{HeaderMap, StatusCode, Uri, header, uri::Authority},
+    response::{IntoResponse, Redirect, Response},
+};
+use axum_server::tls_rustls::RustlsConfig;
+use tokio::sync::watch;
+
+use crate::infrastructure::config::TlsConfig;
+
+pub async fn load_rustls_config(tls: &TlsConfig) -> anyhow::Result<RustlsConfig> {
+    // Several providers may be compiled in through dependencies, so pick one explicitly.
+    let _ = rustls::crypto::ring::default_provider().install_default();
+
+    RustlsConfig::from_pem_file(&tls.cert_path, &tls.key_path)
+        .await
+        .with_context(|| {
+            format!(
+                "failed to load TLS certificate {:?} / key {:?}",
+                tls.cert_path, tls.key_path
+            )
+        })
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Polls the certificate and key files and swaps them into the running
+/// listener when either changes (e.g. after a certbot renewal).
+pub fn spawn_cert_reloader(
+    rustls_config: RustlsConfig,
+    tls: TlsConfig,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    tokio::spawn(async move {
+        let stamp = |cert: &PathBuf, key: &PathBuf| (modified_at(cert), modified_at(key));
+
+        let mut last_seen = stamp(&tls.cert_path, &tls.key_path);
+        let mut interval = tokio::time::interval(tls.reload_interval);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {},
+                _ = shutdown.wait_for(|d| *d) => break,
+            }
+
+            let current = stamp(&tls.cert_path, &tls.key_path);
+
+            if current == last_seen {
+                continue;
+            }
+
+            match rustls_config
+                .reload_from_pem_file(&tls.cert_path, &tls.key_path)
+                .await
+            {
+                Ok(()) => {
+                    println!("->> {:<12} - certificate reloaded", "TLS");
+                    last_seen = current;
+                }
+                // Keep serving the old certificate; a half-written file is retried on the next tick.
+                Err(e) => println!("->> {:<12} - certificate reload failed: {e}", "TLS"),
+            }
+        }
+    });
+}
+
+/// Plain HTTP listener that sends every request to the HTTPS port.
+pub async fn serve_https_redirect(
+    redirect_port: u16,
+    https_port: u16,
+    mut shutdown: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    let addr = SocketAddr::from(([0, 0, 0, 0], redirect_port));
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("failed to bind HTTP redirect listener on {addr}"))?;
+
+    let router = Router::new().fallback(move |headers: HeaderMap, uri: Uri| async move {
+        redirect_to_https(&headers, &uri, https_port)
+    });
+
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async move {
+            let _ = shutdown.wait_for(|d| *d).await;
+        })
+        .await?;
+
+    Ok(())
+}
+
+fn redirect_to_https(headers: &HeaderMap, uri: &Uri, https_port: u16) -> Response {
+    let Some(host) = headers
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.parse::<Authority>().ok())
+    else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+
+    // Keeps the brackets of an IPv6 host
+    let host = host.host();
+
+    let path_and_query = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+
+    let location = match https_port {
+        443 => format!("https://{host}{path_and_query}"),
+        port => format!("https://{host}:{port}{path_and_query}"),
+    };
+
+    Redirect::permanent(&location).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redirect_keeps_ipv6_host() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, "[::1]:8080".parse().unwrap());
+
+        let response = redirect_to_https(&headers, &Uri::from_static("/"), 443);
+
+        assert_eq!(response.headers()[header::LOCATION], "https://[::1]/");
+    }
+
+    #[test]
+    fn test_redirect_keeps_path_and_query() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, "chat.example.org:80".parse().unwrap());
+
+        let uri: Uri = "/api/chats?page=2".parse().unwrap();
+
+        let response = redirect_to_https(&headers, &uri, 8443);
+
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.headers()[header::LOCATION],
+            "https://chat.example.org:8443/api/chats?page=2"
+        );
+    }
+
+    #[test]
+    fn test_redirect_without_host_is_bad_request() {
+        let response = redirect_to_https(&HeaderMap::new(), &Uri::from_static("/"), 443);
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
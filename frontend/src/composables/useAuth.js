@@ -12,7 +12,7 @@ export function useAuth() {
     async function fetchUser() {
 
     try {
-      const res = await fetchApi('/api/user')
+      const res = await fetchApi('/api/v1/users/me')
 
       if (res.ok) {
         user.value = await res.json()
@@ -33,7 +33,7 @@ export function useAuth() {
   async function logout() {
     try {
 
-      await fetchApi('/api/logout', { method: 'POST' })
+      await fetchApi('/api/v1/logout', { method: 'POST' })
     } catch (err) {
       console.error("Logout failed", err)
     } finally {
@@ -2,11 +2,25 @@ const headers = {
     'Content-Type': 'application/json',
 }
 
+const SAFE_METHODS = ['GET', 'HEAD', 'OPTIONS']
+
+function readCookie(name) {
+    const entry = document.cookie
+        .split('; ')
+        .find((c) => c.startsWith(`${name}=`))
+
+    return entry ? decodeURIComponent(entry.slice(name.length + 1)) : null
+}
+
 export async function fetchApi(url, options = {}) {
+    const method = (options.method || 'GET').toUpperCase()
+    const csrfToken = readCookie('csrf-token')
+
     const config = {
         ...options,
         headers: {
             ...headers,
+            ...(!SAFE_METHODS.includes(method) && csrfToken ? { 'X-CSRF-Token': csrfToken } : {}),
             ...options.headers
         },
         credentials: 'include',
@@ -30,4 +44,4 @@ export async function fetchApi(url, options = {}) {
 
     return response
 
-}
\ No newline at end of file
+}